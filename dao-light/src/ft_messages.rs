@@ -1,34 +1,54 @@
 use ft_io::*;
 use gstd::{msg, ActorId};
 
-pub async fn transfer_from_tokens(token_id: &ActorId, from: &ActorId, to: &ActorId, amount: u128) {
-    let _transfer_response: FTEvent = msg::send_and_wait_for_reply(
+pub async fn transfer_from_tokens(
+    token_id: &ActorId,
+    from: &ActorId,
+    to: &ActorId,
+    amount: u128,
+) -> Result<(), FTError> {
+    let transfer_response: FTEvent = msg::send_and_wait_for_reply(
         *token_id,
-        FTAction::Transfer {
+        FTAction::TransferFrom {
             from: *from,
             to: *to,
             amount,
+            tx_id: None,
         },
         0,
     )
     .unwrap()
     .await
-    .expect("Error in transfer");
+    .expect("Error in decoding reply");
+    match transfer_response {
+        FTEvent::TransferFrom { .. } => Ok(()),
+        FTEvent::Err(error) => Err(error),
+        _ => panic!("Unexpected reply to a transfer"),
+    }
 }
 
-pub async fn transfer_tokens(token_id: &ActorId, from: &ActorId, to: &ActorId, amount: u128) {
-    let _transfer_response: FTEvent = msg::send_and_wait_for_reply(
+pub async fn transfer_tokens(
+    token_id: &ActorId,
+    to: &ActorId,
+    amount: u128,
+) -> Result<(), FTError> {
+    let transfer_response: FTEvent = msg::send_and_wait_for_reply(
         *token_id,
         FTAction::Transfer {
-            from: *from,
             to: *to,
             amount,
+            tx_id: None,
         },
         0,
     )
     .unwrap()
     .await
-    .expect("Error in transfer");
+    .expect("Error in decoding reply");
+    match transfer_response {
+        FTEvent::Transfer { .. } => Ok(()),
+        FTEvent::Err(error) => Err(error),
+        _ => panic!("Unexpected reply to a transfer"),
+    }
 }
 
 pub async fn approve_tokens(token_id: &ActorId, to: &ActorId, amount: u128) {
@@ -39,6 +59,16 @@ pub async fn approve_tokens(token_id: &ActorId, to: &ActorId, amount: u128) {
             .expect("Error in approve tokens");
 }
 
+/// Panics with the reason why the token program rejected a transfer from the DAO
+pub fn transfer_failed(error: FTError) -> ! {
+    match error {
+        FTError::InsufficientBalance => panic!("DAO doesn't have enough tokens for the transfer"),
+        FTError::Paused => panic!("The token is paused"),
+        FTError::AccountFrozen(_) => panic!("DAO account is frozen in the token"),
+        error => panic!("Error in transfer: {error:?}"),
+    }
+}
+
 pub async fn balance(token_id: &ActorId, account: &ActorId) -> u128 {
     let balance_response: FTEvent =
         msg::send_and_wait_for_reply(*token_id, FTAction::BalanceOf(*account), 0)
@@ -1,6 +1,7 @@
 #![no_std]
 use codec::{Decode, Encode};
 pub use dao_light_io::*;
+use ft_io::FTError;
 use gstd::{exec, msg, prelude::*, ActorId, String};
 use scale_info::TypeInfo;
 pub mod state;
@@ -52,13 +53,23 @@ impl Dao {
     /// * `amount`: the number of fungible tokens that user wants to deposit to DAO
     async fn deposit(&mut self, amount: u128) {
         let share = self.calculate_share(amount).await;
-        transfer_tokens(
+        match transfer_from_tokens(
             &self.approved_token_program_id,
             &msg::source(),
             &exec::program_id(),
             amount,
         )
-        .await;
+        .await
+        {
+            Ok(()) => {}
+            Err(FTError::InsufficientBalance) => {
+                panic!("Member doesn't have enough tokens to deposit")
+            }
+            Err(FTError::InsufficientAllowance) => {
+                panic!("Member must approve the DAO to spend the deposit")
+            }
+            Err(error) => panic!("Error in transfer: {error:?}"),
+        }
         self.members
             .entry(msg::source())
             .and_modify(|member| member.shares += share)
@@ -246,13 +257,15 @@ impl Dao {
 
         // if funding propoposal has passed
         if proposal.did_pass {
-            transfer_tokens(
+            if let Err(error) = transfer_tokens(
                 &self.approved_token_program_id,
-                &exec::program_id(),
                 &proposal.applicant,
                 proposal.amount,
             )
-            .await;
+            .await
+            {
+                transfer_failed(error);
+            }
         }
         self.locked_funds = self.locked_funds.saturating_sub(proposal.amount);
         msg::reply(
@@ -295,13 +308,11 @@ impl Dao {
         }
         member.shares = member.shares.saturating_sub(amount);
         let funds = self.redeemable_funds(amount).await;
-        transfer_tokens(
-            &self.approved_token_program_id,
-            &exec::program_id(),
-            &msg::source(),
-            funds,
-        )
-        .await;
+        if let Err(error) =
+            transfer_tokens(&self.approved_token_program_id, &msg::source(), funds).await
+        {
+            transfer_failed(error);
+        }
         self.total_shares = self.total_shares.saturating_sub(amount);
         msg::reply(
             DaoEvent::RageQuit {
@@ -17,12 +17,30 @@ pub fn init_fungible_token(sys: &System) {
         InitConfig {
             name: String::from("MyToken"),
             symbol: String::from("MTK"),
+            decimals: 18,
+            admin: MEMBERS[0].into(),
+            minters: vec![MEMBERS[0].into()],
+            max_supply: None,
         },
     );
 
     assert!(res.log().is_empty());
     MEMBERS.iter().for_each(|member| {
-        let res = ft.send(*member, FTAction::Mint(10000000));
+        let res = ft.send(
+            MEMBERS[0],
+            FTAction::Mint {
+                to: (*member).into(),
+                amount: 10000000,
+            },
+        );
+        assert!(!res.main_failed());
+        let res = ft.send(
+            *member,
+            FTAction::Approve {
+                to: 2.into(),
+                amount: 10000000,
+            },
+        );
         assert!(!res.main_failed());
     });
 }
@@ -1,6 +1,7 @@
 #![no_std]
 use codec::{Decode, Encode};
 pub use dao_io::*;
+use ft_io::FTError;
 use gstd::{exec, msg, prelude::*, ActorId, String};
 use scale_info::TypeInfo;
 pub mod state;
@@ -103,13 +104,23 @@ impl Dao {
         }
 
         // transfer applicant tokens to DAO contract
-        transfer_tokens(
+        match transfer_from_tokens(
             &self.approved_token_program_id,
             applicant,
             &exec::program_id(),
             token_tribute,
         )
-        .await;
+        .await
+        {
+            Ok(()) => {}
+            Err(FTError::InsufficientBalance) => {
+                panic!("Applicant doesn't have enough tokens for the token tribute")
+            }
+            Err(FTError::InsufficientAllowance) => {
+                panic!("Applicant must approve the DAO to spend the token tribute")
+            }
+            Err(error) => panic!("Error in transfer: {error:?}"),
+        }
 
         let mut starting_period = exec::block_timestamp();
         // compute startingPeriod for proposal
@@ -337,25 +348,27 @@ impl Dao {
                 .entry(proposal.applicant)
                 .or_insert(proposal.applicant);
             self.total_shares = self.total_shares.saturating_add(proposal.shares_requested);
-        } else {
-            transfer_tokens(
-                &self.approved_token_program_id,
-                &exec::program_id(),
-                &proposal.applicant,
-                proposal.token_tribute,
-            )
-            .await;
+        } else if let Err(error) = transfer_tokens(
+            &self.approved_token_program_id,
+            &proposal.applicant,
+            proposal.token_tribute,
+        )
+        .await
+        {
+            transfer_failed(error);
         }
 
         // if funding propoposal has passed
         if proposal.did_pass && !proposal.is_membership_proposal {
-            transfer_tokens(
+            if let Err(error) = transfer_tokens(
                 &self.approved_token_program_id,
-                &exec::program_id(),
                 &proposal.applicant,
                 proposal.amount,
             )
-            .await;
+            .await
+            {
+                transfer_failed(error);
+            }
         }
         msg::reply(
             DaoEvent::ProcessProposal {
@@ -394,13 +407,11 @@ impl Dao {
         }
         member.shares = member.shares.saturating_sub(amount);
         let funds = self.redeemable_funds(amount).await;
-        transfer_tokens(
-            &self.approved_token_program_id,
-            &exec::program_id(),
-            &msg::source(),
-            funds,
-        )
-        .await;
+        if let Err(error) =
+            transfer_tokens(&self.approved_token_program_id, &msg::source(), funds).await
+        {
+            transfer_failed(error);
+        }
         self.total_shares = self.total_shares.saturating_sub(amount);
         msg::reply(
             DaoEvent::RageQuit {
@@ -447,13 +458,11 @@ impl Dao {
         proposal.token_tribute = 0;
         proposal.cancelled = true;
 
-        transfer_tokens(
-            &self.approved_token_program_id,
-            &exec::program_id(),
-            &proposal.applicant,
-            amount,
-        )
-        .await;
+        if let Err(error) =
+            transfer_tokens(&self.approved_token_program_id, &proposal.applicant, amount).await
+        {
+            transfer_failed(error);
+        }
 
         msg::reply(
             DaoEvent::Cancel {
@@ -493,13 +502,11 @@ impl Dao {
         proposal.token_tribute = 0;
         proposal.aborted = true;
 
-        transfer_tokens(
-            &self.approved_token_program_id,
-            &exec::program_id(),
-            &msg::source(),
-            amount,
-        )
-        .await;
+        if let Err(error) =
+            transfer_tokens(&self.approved_token_program_id, &msg::source(), amount).await
+        {
+            transfer_failed(error);
+        }
 
         msg::reply(
             DaoEvent::Abort {
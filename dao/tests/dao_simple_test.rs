@@ -15,12 +15,22 @@ fn init_fungible_token(sys: &System) {
         InitConfig {
             name: String::from("MyToken"),
             symbol: String::from("MTK"),
+            decimals: 18,
+            admin: 100001.into(),
+            minters: vec![100001.into()],
+            max_supply: None,
         },
     );
 
     assert!(res.log().is_empty());
 
-    let res = ft.send(4, FTAction::Mint(10000000));
+    let res = ft.send(
+        100001,
+        FTAction::Mint {
+            to: 4.into(),
+            amount: 10000000,
+        },
+    );
     assert!(!res.main_failed());
     let res = ft.send(
         4,
@@ -14,6 +14,10 @@ fn init_fungible_token(sys: &System) {
         InitConfig {
             name: String::from("MyToken"),
             symbol: String::from("MTK"),
+            decimals: 18,
+            admin: 100001.into(),
+            minters: vec![100001.into()],
+            max_supply: None,
         },
     );
 
@@ -38,7 +42,13 @@ fn init_dao(sys: &System) {
 }
 
 fn mint_tokens(ft: &Program, user: u64) {
-    let res = ft.send(user, FTAction::Mint(10000));
+    let res = ft.send(
+        100001,
+        FTAction::Mint {
+            to: user.into(),
+            amount: 10000,
+        },
+    );
     assert!(!res.main_failed());
 
     let res = ft.send(
@@ -14,11 +14,7 @@ impl WasmProgram for FungibleToken {
     fn handle(&mut self, payload: Vec<u8>) -> Result<Option<Vec<u8>>, &'static str> {
         let res = FTAction::decode(&mut &payload[..]).map_err(|_| "Can't decode")?;
         match res {
-            FTAction::Transfer {
-                from: _,
-                to: _,
-                amount: _,
-            } => {
+            FTAction::Transfer { .. } => {
                 return Ok(Some(
                     FTEvent::Transfer {
                         from: 3.into(),
@@ -28,6 +24,17 @@ impl WasmProgram for FungibleToken {
                     .encode(),
                 ));
             }
+            FTAction::TransferFrom { .. } => {
+                return Ok(Some(
+                    FTEvent::TransferFrom {
+                        from: 3.into(),
+                        to: 3.into(),
+                        amount: 10000,
+                        allowance: 0,
+                    }
+                    .encode(),
+                ));
+            }
             FTAction::BalanceOf(_) => {
                 return Ok(Some(FTEvent::Balance(10000).encode()));
             }
@@ -10,6 +10,15 @@ pub enum Action {
     Buy,
     Create(CreateConfig),
     ForceStop,
+    /// Sent by an NFT contract when a token is safely transferred to the auction.
+    /// Only the token of the active auction is accepted.
+    #[codec(index = 255)]
+    OnNftReceived {
+        operator: ActorId,
+        from: ActorId,
+        token_id: U256,
+        data: Vec<u8>,
+    },
 }
 
 #[derive(Debug, Encode, Decode, TypeInfo)]
@@ -23,6 +32,8 @@ pub enum Event {
         token_owner: ActorId,
         token_id: U256,
     },
+    #[codec(index = 255)]
+    NftReceived { token_id: U256 },
 }
 
 #[derive(Debug, Encode, Decode, TypeInfo)]
@@ -128,6 +128,15 @@ impl Auction {
         .unwrap();
     }
 
+    fn on_nft_received(&self, token_id: U256) {
+        if !self.is_active || msg::source() != self.nft.contract_id || token_id != self.nft.token_id
+        {
+            panic!("that token is not on the auction");
+        }
+
+        msg::reply(Event::NftReceived { token_id }, 0).unwrap();
+    }
+
     fn info(&self) -> AuctionInfo {
         AuctionInfo {
             nft_contract_actor_id: self.nft.contract_id,
@@ -171,6 +180,7 @@ async unsafe fn main() {
         Action::Buy => auction.buy().await,
         Action::Create(config) => auction.renew_contract(config),
         Action::ForceStop => auction.force_stop(),
+        Action::OnNftReceived { token_id, .. } => auction.on_nft_received(token_id),
     }
 }
 
@@ -43,6 +43,8 @@ fn init_nft(sys: &System, owner: u64) {
             name: String::from("MyToken"),
             symbol: String::from("MTK"),
             base_uri: String::from(""),
+            history_retention: 0,
+            transferable: true,
         },
     );
 
@@ -215,3 +217,61 @@ fn stop_from_other_user() {
 
     assert!(result.main_failed());
 }
+
+#[test]
+fn safe_transfer_to_auction() {
+    let sys = System::new();
+    let auction = init(&sys);
+    let nft = sys.get_program(2);
+
+    let result = nft.send(
+        USERS[0],
+        nft_example_io::Action::SafeTransfer {
+            to: 1.into(),
+            token_id: 0.into(),
+            data: vec![],
+        },
+    );
+    assert!(result.contains(&(
+        USERS[0],
+        nft_example_io::Event::Transfer {
+            from: USERS[0].into(),
+            to: 1.into(),
+            token_id: 0.into(),
+        }
+        .encode()
+    )));
+
+    // the auction rejects tokens that are not on the auction
+    nft.send(USERS[0], nft_example_io::Action::Mint);
+    let result = nft.send(
+        USERS[0],
+        nft_example_io::Action::SafeTransfer {
+            to: 1.into(),
+            token_id: 1.into(),
+            data: vec![],
+        },
+    );
+    assert!(result.contains(&(
+        USERS[0],
+        nft_example_io::Event::TransferRejected {
+            from: USERS[0].into(),
+            to: 1.into(),
+            token_id: 1.into(),
+        }
+        .encode()
+    )));
+    let result = nft.send(USERS[0], nft_example_io::Action::OwnerOf(1.into()));
+    assert!(result.contains(&(
+        USERS[0],
+        nft_example_io::Event::OwnerOf(USERS[0].into()).encode()
+    )));
+
+    // the auction sells the token it holds
+    auction.send_with_value(USERS[1], Action::Buy, 1_000_000_000);
+    let result = nft.send(USERS[0], nft_example_io::Action::OwnerOf(0.into()));
+    assert!(result.contains(&(
+        USERS[0],
+        nft_example_io::Event::OwnerOf(USERS[1].into()).encode()
+    )));
+}
@@ -2,12 +2,7 @@
 
 use escrow_io::*;
 use ft_io::*;
-use gstd::{
-    async_main, exec,
-    msg::{self, CodecMessageFuture},
-    prelude::*,
-    ActorId,
-};
+use gstd::{async_main, exec, msg, prelude::*, ActorId};
 
 #[derive(PartialEq)]
 enum State {
@@ -16,13 +11,63 @@ enum State {
     Completed,
 }
 
-fn transfer_tokens(
+async fn transfer_tokens(
+    ft_program_id: ActorId,
+    to: ActorId,
+    amount: u128,
+    tx_id: u64,
+) -> Result<(), FTError> {
+    send_transfer(
+        ft_program_id,
+        FTAction::Transfer {
+            to,
+            amount,
+            tx_id: Some(tx_id),
+        },
+    )
+    .await
+}
+
+async fn transfer_from_tokens(
     ft_program_id: ActorId,
     from: ActorId,
     to: ActorId,
     amount: u128,
-) -> CodecMessageFuture<FTEvent> {
-    msg::send_and_wait_for_reply(ft_program_id, FTAction::Transfer { from, to, amount }, 0).unwrap()
+    tx_id: u64,
+) -> Result<(), FTError> {
+    send_transfer(
+        ft_program_id,
+        FTAction::TransferFrom {
+            from,
+            to,
+            amount,
+            tx_id: Some(tx_id),
+        },
+    )
+    .await
+}
+
+async fn send_transfer(ft_program_id: ActorId, action: FTAction) -> Result<(), FTError> {
+    let reply: FTEvent = msg::send_and_wait_for_reply(ft_program_id, action, 0)
+        .unwrap()
+        .await
+        .expect("Unable to decode FTEvent");
+    match reply {
+        FTEvent::Transfer { .. } | FTEvent::TransferFrom { .. } => Ok(()),
+        FTEvent::Err(error) => Err(error),
+        _ => panic!("Unexpected reply to a transfer"),
+    }
+}
+
+/// Returns the ID of the contract transfer in progress or a new one if there is none.
+/// The ID is saved before waiting for the transfer, so if the step fails after the tokens were moved,
+/// its retry gets the original reply from the token program instead of moving them again.
+fn pending_tx_id(contract: &mut Contract, transaction_id: &mut u64) -> u64 {
+    *contract.tx_id.get_or_insert_with(|| {
+        let tx_id = *transaction_id;
+        *transaction_id += 1;
+        tx_id
+    })
 }
 
 fn get(contracts: &mut BTreeMap<u128, Contract>, contract_id: u128) -> &mut Contract {
@@ -38,6 +83,7 @@ struct Escrow {
     ft_program_id: ActorId,
     contracts: BTreeMap<u128, Contract>,
     id_nonce: u128,
+    transaction_id: u64,
 }
 
 impl Escrow {
@@ -65,6 +111,7 @@ impl Escrow {
                 seller,
                 amount,
                 state: State::AwaitingDeposit,
+                tx_id: None,
             },
         );
 
@@ -77,6 +124,7 @@ impl Escrow {
     /// Requirements:
     /// * `msg::source()` must be a buyer saved in a contract.
     /// * Contract must not be paid or completed.
+    /// * A buyer must approve the escrow to spend `amount` of its tokens.
     ///
     /// Arguments:
     /// * `contract_id`: a contract ID.
@@ -91,16 +139,28 @@ impl Escrow {
             panic!("Contract can't take deposit if it's paid or completed");
         }
 
-        transfer_tokens(
+        let tx_id = pending_tx_id(contract, &mut self.transaction_id);
+        match transfer_from_tokens(
             self.ft_program_id,
             contract.buyer,
             exec::program_id(),
             contract.amount,
+            tx_id,
         )
         .await
-        .expect("Error when taking a deposit");
+        {
+            Ok(()) => {}
+            Err(FTError::InsufficientBalance) => {
+                panic!("A buyer doesn't have enough tokens to make a deposit")
+            }
+            Err(FTError::InsufficientAllowance) => {
+                panic!("A buyer must approve the escrow to spend the deposit")
+            }
+            Err(error) => panic!("Error when taking a deposit: {error:?}"),
+        }
 
         contract.state = State::AwaitingConfirmation;
+        contract.tx_id = None;
 
         msg::reply(
             EscrowEvent::Deposited {
@@ -132,16 +192,15 @@ impl Escrow {
             panic!("Contract can't be confirmed if it's not paid or completed");
         }
 
-        transfer_tokens(
-            self.ft_program_id,
-            exec::program_id(),
-            contract.seller,
-            contract.amount,
-        )
-        .await
-        .expect("Error when confirming a contract");
+        let tx_id = pending_tx_id(contract, &mut self.transaction_id);
+        if let Err(error) =
+            transfer_tokens(self.ft_program_id, contract.seller, contract.amount, tx_id).await
+        {
+            panic!("Error when confirming a contract: {error:?}");
+        }
 
         contract.state = State::Completed;
+        contract.tx_id = None;
 
         msg::reply(
             EscrowEvent::Confirmed {
@@ -174,16 +233,15 @@ impl Escrow {
             panic!("Contract can't be refunded if it's not paid or completed");
         }
 
-        transfer_tokens(
-            self.ft_program_id,
-            exec::program_id(),
-            contract.buyer,
-            contract.amount,
-        )
-        .await
-        .expect("Error when refunding a contract");
+        let tx_id = pending_tx_id(contract, &mut self.transaction_id);
+        if let Err(error) =
+            transfer_tokens(self.ft_program_id, contract.buyer, contract.amount, tx_id).await
+        {
+            panic!("Error when refunding a contract: {error:?}");
+        }
 
         contract.state = State::AwaitingDeposit;
+        contract.tx_id = None;
 
         msg::reply(
             EscrowEvent::Refunded {
@@ -233,6 +291,8 @@ struct Contract {
     seller: ActorId,
     state: State,
     amount: u128,
+    /// The ID of the transfer that was sent but hasn't completed its step yet
+    tx_id: Option<u64>,
 }
 
 static mut ESCROW: Option<Escrow> = None;
@@ -8,6 +8,7 @@ fn cancel_paid() {
     let ft_program = init_fungible_tokens(&system);
 
     mint(&ft_program, BUYER[0], AMOUNT[0]);
+    approve(&ft_program, BUYER[0], AMOUNT[0]);
     create(
         &escrow_program,
         CONTRACT[0],
@@ -29,6 +30,7 @@ fn foreign_user_cancel() {
     let ft_program = init_fungible_tokens(&system);
 
     mint(&ft_program, BUYER[0], AMOUNT[0]);
+    approve(&ft_program, BUYER[0], AMOUNT[0]);
     create(
         &escrow_program,
         CONTRACT[0],
@@ -48,6 +50,7 @@ fn interact_after_cancel() {
     let ft_program = init_fungible_tokens(&system);
 
     mint(&ft_program, BUYER[0], AMOUNT[0]);
+    approve(&ft_program, BUYER[0], AMOUNT[0]);
     create(
         &escrow_program,
         CONTRACT[0],
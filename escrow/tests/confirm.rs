@@ -8,6 +8,7 @@ fn not_buyer_confirm() {
     let ft_program = init_fungible_tokens(&system);
 
     mint(&ft_program, BUYER[0], AMOUNT[0]);
+    approve(&ft_program, BUYER[0], AMOUNT[0]);
     create(
         &escrow_program,
         CONTRACT[0],
@@ -31,6 +32,7 @@ fn double_confirm() {
     let ft_program = init_fungible_tokens(&system);
 
     mint(&ft_program, BUYER[0], AMOUNT[0]);
+    approve(&ft_program, BUYER[0], AMOUNT[0]);
     create(
         &escrow_program,
         CONTRACT[0],
@@ -53,6 +55,7 @@ fn confirm_before_deposit() {
     let ft_program = init_fungible_tokens(&system);
 
     mint(&ft_program, BUYER[0], AMOUNT[0]);
+    approve(&ft_program, BUYER[0], AMOUNT[0]);
     create(
         &escrow_program,
         CONTRACT[0],
@@ -73,6 +76,7 @@ fn interact_after_confirm() {
     let ft_program = init_fungible_tokens(&system);
 
     mint(&ft_program, BUYER[0], AMOUNT[0]);
+    approve(&ft_program, BUYER[0], AMOUNT[0]);
     create(
         &escrow_program,
         CONTRACT[0],
@@ -28,6 +28,7 @@ fn double_deposit() {
 
     // Purposely make it possible for a buyer to pay twice
     mint(&ft_program, BUYER[0], AMOUNT[0] * 2);
+    approve(&ft_program, BUYER[0], AMOUNT[0] * 2);
     create(
         &escrow_program,
         CONTRACT[0],
@@ -60,3 +61,32 @@ fn not_buyer_deposit() {
     deposit_fail(&escrow_program, CONTRACT[0], BUYER[1]);
     deposit_fail(&escrow_program, CONTRACT[0], SELLER[0]);
 }
+
+#[test]
+fn deposit_after_failure() {
+    let system = init_system();
+    let escrow_program = init_escrow(&system);
+    let ft_program = init_fungible_tokens(&system);
+
+    mint(&ft_program, BUYER[0], AMOUNT[0]);
+    create(
+        &escrow_program,
+        CONTRACT[0],
+        SELLER[0],
+        BUYER[0],
+        SELLER[0],
+        AMOUNT[0],
+    );
+    // Should fail because a buyer hasn't approved the escrow yet
+    deposit_fail(&escrow_program, CONTRACT[0], BUYER[0]);
+    // The retry takes the deposit once
+    approve(&ft_program, BUYER[0], AMOUNT[0]);
+    deposit(&escrow_program, CONTRACT[0], BUYER[0], AMOUNT[0]);
+    check_balance(&ft_program, BUYER[0], 0);
+
+    // A refunded contract takes a new deposit
+    refund(&escrow_program, CONTRACT[0], BUYER[0], SELLER[0], AMOUNT[0]);
+    approve(&ft_program, BUYER[0], AMOUNT[0]);
+    deposit(&escrow_program, CONTRACT[0], BUYER[0], AMOUNT[0]);
+    check_balance(&ft_program, BUYER[0], 0);
+}
@@ -10,7 +10,9 @@ fn two_different_escrows() {
     let ft_program = init_fungible_tokens(&system);
 
     mint(&ft_program, BUYER[0], AMOUNT[0] + AMOUNT_REMAINDER);
+    approve(&ft_program, BUYER[0], AMOUNT[0] + AMOUNT_REMAINDER);
     mint(&ft_program, BUYER[1], AMOUNT[1] + AMOUNT_REMAINDER);
+    approve(&ft_program, BUYER[1], AMOUNT[1] + AMOUNT_REMAINDER);
 
     create(
         &escrow_program,
@@ -49,6 +51,7 @@ fn reuse_after_refund() {
     let ft_program = init_fungible_tokens(&system);
 
     mint(&ft_program, BUYER[0], AMOUNT[0]);
+    approve(&ft_program, BUYER[0], AMOUNT[0]);
     create(
         &escrow_program,
         CONTRACT[0],
@@ -62,6 +65,7 @@ fn reuse_after_refund() {
     refund(&escrow_program, CONTRACT[0], BUYER[0], SELLER[0], AMOUNT[0]);
     check_balance(&ft_program, BUYER[0], AMOUNT[0]);
 
+    approve(&ft_program, BUYER[0], AMOUNT[0]);
     deposit(&escrow_program, CONTRACT[0], BUYER[0], AMOUNT[0]);
     confirm(&escrow_program, CONTRACT[0], BUYER[0], SELLER[0], AMOUNT[0]);
 }
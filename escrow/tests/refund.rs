@@ -8,6 +8,7 @@ fn refund_not_paid() {
     let ft_program = init_fungible_tokens(&system);
 
     mint(&ft_program, BUYER[0], AMOUNT[0]);
+    approve(&ft_program, BUYER[0], AMOUNT[0]);
     create(
         &escrow_program,
         CONTRACT[0],
@@ -27,6 +28,7 @@ fn not_seller_refund() {
     let ft_program = init_fungible_tokens(&system);
 
     mint(&ft_program, BUYER[0], AMOUNT[0]);
+    approve(&ft_program, BUYER[0], AMOUNT[0]);
     create(
         &escrow_program,
         CONTRACT[0],
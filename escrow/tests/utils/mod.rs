@@ -4,6 +4,7 @@ use gstd::Encode;
 use gstd::String;
 use gtest::{Program, System};
 
+pub const ESCROW: u64 = 1;
 pub const FT: u64 = 2;
 pub const FOREIGN_USER: u64 = 1337;
 pub const BUYER: [u64; 2] = [12, 34];
@@ -30,6 +31,10 @@ pub fn init_fungible_tokens(sys: &System) -> Program {
             InitConfig {
                 name: String::from("MyToken"),
                 symbol: String::from("MTK"),
+                decimals: 18,
+                admin: FOREIGN_USER.into(),
+                minters: vec![FOREIGN_USER.into()],
+                max_supply: None,
             },
         )
         .log()
@@ -177,14 +182,42 @@ pub fn check_balance(ft_program: &Program, from: u64, amount: u128) {
         .contains(&(from, FTEvent::Balance(amount).encode())));
 }
 
-pub fn mint(ft_program: &Program, from: u64, amount: u128) {
-    assert!(ft_program.send(from, FTAction::Mint(amount)).contains(&(
-        from,
-        FTEvent::Transfer {
-            from: 0.into(),
-            to: from.into(),
-            amount,
-        }
-        .encode()
-    )));
+pub fn mint(ft_program: &Program, to: u64, amount: u128) {
+    assert!(ft_program
+        .send(
+            FOREIGN_USER,
+            FTAction::Mint {
+                to: to.into(),
+                amount,
+            },
+        )
+        .contains(&(
+            FOREIGN_USER,
+            FTEvent::Transfer {
+                from: 0.into(),
+                to: to.into(),
+                amount,
+            }
+            .encode()
+        )));
+}
+
+pub fn approve(ft_program: &Program, from: u64, amount: u128) {
+    assert!(ft_program
+        .send(
+            from,
+            FTAction::Approve {
+                to: ESCROW.into(),
+                amount,
+            },
+        )
+        .contains(&(
+            from,
+            FTEvent::Approve {
+                from: from.into(),
+                to: ESCROW.into(),
+                amount,
+            }
+            .encode()
+        )));
 }
@@ -8,39 +8,254 @@ use scale_info::TypeInfo;
 pub struct InitConfig {
     pub name: String,
     pub symbol: String,
+    pub decimals: u8,
+    /// Account allowed to manage the set of minters.
+    pub admin: ActorId,
+    /// Accounts allowed to mint tokens.
+    pub minters: Vec<ActorId>,
+    /// Maximum amount of tokens that can ever be in circulation.
+    pub max_supply: Option<u128>,
 }
 
 #[derive(Debug, Decode, Encode, TypeInfo)]
 pub enum FTAction {
-    Mint(u128),
+    /// Mints `amount` tokens to `to`. Only minters can mint tokens.
+    Mint {
+        to: ActorId,
+        amount: u128,
+    },
+    /// Mints tokens to every recipient in the list. Only minters can mint tokens.
+    BatchMint(Vec<(ActorId, u128)>),
     Burn(u128),
+    /// Transfers `amount` tokens from `msg::source()` to `to`.
+    ///
+    /// If `tx_id` is set, a successful transfer is remembered for `msg::source()`
+    /// and a retry with the same `tx_id` gets the original reply without moving tokens again.
+    /// The retry must be the same action, otherwise `FTError::TxIdReused` is replied.
     Transfer {
+        to: ActorId,
+        amount: u128,
+        tx_id: Option<u64>,
+    },
+    /// Transfers `amount` tokens from `from` to `to`,
+    /// spending the allowance `from` granted to `msg::source()`.
+    ///
+    /// `tx_id` works the same way as for `Transfer`.
+    ///
+    /// The reply is the `TransferFrom` event with the allowance left after the transfer.
+    TransferFrom {
         from: ActorId,
         to: ActorId,
         amount: u128,
+        tx_id: Option<u64>,
     },
+    /// Transfers tokens from `msg::source()` to every recipient in the list.
+    /// Either all the transfers are applied or none of them.
+    BatchTransfer(Vec<(ActorId, u128)>),
+    /// Sets the allowance of `to` over the tokens of `msg::source()` to `amount`.
     Approve {
         to: ActorId,
         amount: u128,
     },
+    /// Increases the allowance of `to` over the tokens of `msg::source()` by `amount`.
+    IncreaseAllowance {
+        to: ActorId,
+        amount: u128,
+    },
+    /// Decreases the allowance of `to` over the tokens of `msg::source()` by `amount`.
+    DecreaseAllowance {
+        to: ActorId,
+        amount: u128,
+    },
+    /// Sets the allowance of `spender` over the tokens of `owner` to `amount`
+    /// on behalf of `owner`, so anyone can relay the approval.
+    ///
+    /// `signature` is the sr25519 signature of `owner` over the SCALE-encoded [`PermitPayload`].
+    /// `nonce` must be the current nonce of `owner`, and the permit can't be used
+    /// after `deadline` (a block timestamp in milliseconds).
+    Permit {
+        owner: ActorId,
+        spender: ActorId,
+        amount: u128,
+        deadline: u64,
+        nonce: u64,
+        signature: [u8; 64],
+    },
     TotalSupply,
     BalanceOf(ActorId),
+    /// Allows the account to mint tokens. Only the admin can add minters.
+    AddMinter(ActorId),
+    /// Revokes the minting rights of the account. Only the admin can remove minters.
+    RemoveMinter(ActorId),
+    /// Passes the admin rights to the account. Only the admin can transfer them.
+    TransferAdmin(ActorId),
+    /// Records the current balances and replies with the ID of the new snapshot.
+    /// Only the admin or the snapshotter can take snapshots.
+    Snapshot,
+    /// Sets the account allowed to take snapshots besides the admin.
+    /// Only the admin can set the snapshotter.
+    SetSnapshotter(Option<ActorId>),
+    /// Blocks transfers, minting and burning. Only the admin can pause the token.
+    Pause,
+    /// Lifts the pause. Only the admin can unpause the token.
+    Unpause,
+    /// Blocks the account from sending its tokens. Only the admin can freeze accounts.
+    Freeze(ActorId),
+    /// Lifts the freeze from the account. Only the admin can unfreeze accounts.
+    Unfreeze(ActorId),
+    /// Locks `amount` tokens of the admin in the token program and vests them to `beneficiary`.
+    /// Nothing vests until `cliff` milliseconds after `start`, then the tokens vest
+    /// linearly so that all of them are vested `duration` milliseconds after `start`.
+    /// Only the admin can create vestings, and a beneficiary can have only one vesting.
+    CreateVesting {
+        beneficiary: ActorId,
+        amount: u128,
+        start: u64,
+        cliff: u64,
+        duration: u64,
+    },
+    /// Transfers the vested tokens that haven't been released yet to `msg::source()`.
+    Release,
+    /// Cancels the vesting of the account: the vested tokens are released
+    /// and the rest is returned to the admin. Only the admin can revoke vestings.
+    RevokeVesting(ActorId),
 }
 
-#[derive(Debug, Encode, Decode, TypeInfo)]
+#[derive(Debug, Clone, Encode, Decode, TypeInfo)]
 pub enum FTEvent {
     Transfer {
         from: ActorId,
         to: ActorId,
         amount: u128,
     },
+    /// `amount` tokens are transferred from `from` to `to` by `TransferFrom`,
+    /// and the allowance `from` granted to `msg::source()` is decreased to `allowance`.
+    TransferFrom {
+        from: ActorId,
+        to: ActorId,
+        amount: u128,
+        allowance: u128,
+    },
+    /// The allowance of `to` over the tokens of `from` is set to `amount`
+    /// by `Approve`, `IncreaseAllowance`, `DecreaseAllowance` or `Permit`.
+    /// When `TransferFrom` spends the allowance, it's reported in the `TransferFrom` event.
     Approve {
         from: ActorId,
         to: ActorId,
         amount: u128,
     },
+    BatchTransfer {
+        from: ActorId,
+        transfers: Vec<(ActorId, u128)>,
+        total: u128,
+    },
     TotalSupply(u128),
     Balance(u128),
+    MinterAdded(ActorId),
+    MinterRemoved(ActorId),
+    AdminTransferred {
+        from: ActorId,
+        to: ActorId,
+    },
+    Snapshot(u64),
+    SnapshotterSet(Option<ActorId>),
+    Paused,
+    Unpaused,
+    Frozen(ActorId),
+    Unfrozen(ActorId),
+    VestingCreated {
+        beneficiary: ActorId,
+        amount: u128,
+    },
+    VestingReleased {
+        beneficiary: ActorId,
+        amount: u128,
+    },
+    VestingRevoked {
+        beneficiary: ActorId,
+        released: u128,
+        returned: u128,
+    },
+    /// Replied instead of executing an action that failed.
+    /// The state is left unchanged.
+    Err(FTError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum FTError {
+    /// The token is paused.
+    Paused,
+    /// The token is not paused.
+    NotPaused,
+    /// The account is frozen and can't send tokens.
+    AccountFrozen(ActorId),
+    /// The account is not frozen.
+    AccountNotFrozen(ActorId),
+    /// The zero address was given as an account.
+    ZeroAddress,
+    /// The amount exceeds the account balance.
+    InsufficientBalance,
+    /// The amount exceeds the allowance granted to `msg::source()`.
+    InsufficientAllowance,
+    /// The batch has no entries.
+    EmptyBatch,
+    /// An amount overflowed `u128`.
+    Overflow,
+    /// Minting would make the total supply exceed the max supply.
+    MaxSupplyExceeded,
+    /// `msg::source()` is not the admin.
+    NotAdmin,
+    /// `msg::source()` or the given account is not a minter.
+    NotMinter,
+    /// The account is already a minter.
+    AlreadyMinter,
+    /// `msg::source()` is neither the admin nor the snapshotter.
+    NotSnapshotter,
+    /// The permit deadline has passed.
+    PermitExpired,
+    /// The permit nonce is not the current nonce of the owner.
+    InvalidNonce,
+    /// The permit signature doesn't match the owner.
+    InvalidSignature,
+    /// The beneficiary already has a vesting.
+    VestingExists,
+    /// The account has no vesting.
+    NoVesting,
+    /// The vesting amount is zero or the cliff is longer than the duration.
+    InvalidVesting,
+    /// No tokens have vested since the last release.
+    NothingToRelease,
+    /// `msg::source()` has already used the transaction ID for a different transfer.
+    TxIdReused,
+}
+
+/// Tokens locked for a beneficiary and released as they vest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct Vesting {
+    /// Amount of tokens locked at creation.
+    pub amount: u128,
+    /// Amount of tokens released to the beneficiary so far.
+    pub released: u128,
+    /// Block timestamp the vesting starts at.
+    pub start: u64,
+    /// Milliseconds after `start` before any tokens vest.
+    pub cliff: u64,
+    /// Milliseconds after `start` when all the tokens are vested.
+    pub duration: u64,
+}
+
+/// The message an owner signs to issue a permit.
+///
+/// `token` is the ID of the fungible token program,
+/// so a permit can't be replayed on another token.
+#[derive(Debug, Encode, Decode, TypeInfo)]
+pub struct PermitPayload {
+    pub token: ActorId,
+    pub owner: ActorId,
+    pub spender: ActorId,
+    pub amount: u128,
+    pub deadline: u64,
+    pub nonce: u64,
 }
 
 #[derive(Debug, Encode, Decode, TypeInfo)]
@@ -50,6 +265,30 @@ pub enum State {
     Decimals,
     TotalSupply,
     BalanceOf(ActorId),
+    Allowance {
+        owner: ActorId,
+        spender: ActorId,
+    },
+    Metadata,
+    /// Returns at most `limit` holders with their balances, skipping the first `offset`.
+    Holders {
+        offset: u32,
+        limit: u32,
+    },
+    /// Returns the balance of `account` at the moment the snapshot was taken.
+    BalanceOfAt {
+        account: ActorId,
+        snapshot_id: u64,
+    },
+    /// Returns the total supply at the moment the snapshot was taken.
+    TotalSupplyAt(u64),
+    IsPaused,
+    IsFrozen(ActorId),
+    /// Returns the nonce the next permit of the account must use.
+    Nonce(ActorId),
+    Vesting(ActorId),
+    /// Returns the vested and unvested amounts of the account vesting.
+    VestedAmount(ActorId),
 }
 
 #[derive(Debug, Encode, Decode, TypeInfo)]
@@ -59,4 +298,20 @@ pub enum StateReply {
     Decimals(u8),
     TotalSupply(u128),
     Balance(u128),
+    Allowance(u128),
+    Metadata {
+        name: String,
+        symbol: String,
+        decimals: u8,
+        total_supply: u128,
+    },
+    Holders(Vec<(ActorId, u128)>),
+    IsPaused(bool),
+    IsFrozen(bool),
+    Nonce(u64),
+    Vesting(Option<Vesting>),
+    VestedAmount {
+        vested: u128,
+        unvested: u128,
+    },
 }
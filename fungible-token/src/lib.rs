@@ -5,8 +5,13 @@ mod tests;
 
 use ft_io::*;
 use gstd::{exec, msg, prelude::*, ActorId};
+use schnorrkel::{PublicKey, Signature};
 
 const ZERO_ID: ActorId = ActorId::new([0u8; 32]);
+/// Signing context used by Substrate wallets for sr25519 signatures.
+const SIGNING_CONTEXT: &[u8] = b"substrate";
+/// Number of the latest transactions whose replies are kept for replaying.
+const MAX_TRANSACTIONS: usize = 1024;
 
 #[derive(Debug, Default)]
 struct FungibleToken {
@@ -14,125 +19,664 @@ struct FungibleToken {
     name: String,
     /// Symbol of the token.
     symbol: String,
+    /// Number of decimals the token uses.
+    decimals: u8,
     /// Total supply of the token.
     total_supply: u128,
     /// Map to hold balances of token holders.
     balances: BTreeMap<ActorId, u128>,
     /// Map to hold allowance information of token holders.
     allowances: BTreeMap<ActorId, BTreeMap<ActorId, u128>>,
+    /// Account allowed to manage the set of minters.
+    admin: ActorId,
+    /// Accounts allowed to mint tokens.
+    minters: BTreeSet<ActorId>,
+    /// Maximum amount of tokens that can ever be in circulation.
+    max_supply: Option<u128>,
+    /// Whether transfers, minting and burning are blocked.
+    paused: bool,
+    /// Accounts blocked from sending their tokens.
+    frozen: BTreeSet<ActorId>,
+    /// ID of the latest snapshot, zero if no snapshots were taken.
+    snapshot_id: u64,
+    /// Account allowed to take snapshots besides the admin.
+    snapshotter: Option<ActorId>,
+    /// Balances of token holders recorded at snapshots, ordered by snapshot ID.
+    balance_checkpoints: BTreeMap<ActorId, Vec<(u64, u128)>>,
+    /// Total supply recorded at snapshots, ordered by snapshot ID.
+    total_supply_checkpoints: Vec<(u64, u128)>,
+    /// Hashes of the latest successful transfers sent with a transaction ID and their replies.
+    transactions: BTreeMap<(ActorId, u64), ([u8; 32], FTEvent)>,
+    /// Keys of `transactions` from the oldest to the newest.
+    transaction_queue: VecDeque<(ActorId, u64)>,
+    /// Nonces the next permits of token holders must use.
+    nonces: BTreeMap<ActorId, u64>,
+    /// Vestings of beneficiaries, their tokens are held by the token program.
+    vestings: BTreeMap<ActorId, Vesting>,
 }
 
 static mut FUNGIBLE_TOKEN: Option<FungibleToken> = None;
 
 impl FungibleToken {
-    /// Executed on receiving `fungible-token-messages::MintInput`.
-    fn mint(&mut self, amount: u128) {
-        self.balances
-            .entry(msg::source())
-            .and_modify(|balance| *balance += amount)
-            .or_insert(amount);
-        self.total_supply += amount;
-        msg::reply(
-            FTEvent::Transfer {
-                from: ZERO_ID,
-                to: msg::source(),
+    /// Executed on receiving `FTAction::Mint`.
+    /// Mints `amount` tokens to `to` account.
+    ///
+    /// Requirements:
+    /// * `msg::source()` must be a minter.
+    /// * The token must not be paused.
+    /// * `to` can't be the zero address.
+    /// * The total supply can't exceed `max_supply`.
+    fn mint(&mut self, to: &ActorId, amount: u128) -> Result<FTEvent, FTError> {
+        self.check_minter()?;
+        if to == &ZERO_ID {
+            return Err(FTError::ZeroAddress);
+        }
+        let total_supply = self.supply_after_mint(amount)?;
+        self.checkpoint_total_supply();
+        self.total_supply = total_supply;
+        self.credit(to, amount);
+        Ok(FTEvent::Transfer {
+            from: ZERO_ID,
+            to: *to,
+            amount,
+        })
+    }
+
+    /// Executed on receiving `FTAction::BatchMint`.
+    /// Mints tokens to every recipient in `mints`.
+    ///
+    /// Requirements:
+    /// * `msg::source()` must be a minter.
+    /// * The token must not be paused.
+    /// * `mints` can't be empty or contain the zero address.
+    /// * The total supply can't exceed `max_supply`.
+    fn batch_mint(&mut self, mints: Vec<(ActorId, u128)>) -> Result<FTEvent, FTError> {
+        self.check_minter()?;
+        let total = batch_total(&mints)?;
+        let total_supply = self.supply_after_mint(total)?;
+        self.checkpoint_total_supply();
+        self.total_supply = total_supply;
+        for (to, amount) in &mints {
+            self.credit(to, *amount);
+        }
+        Ok(FTEvent::BatchTransfer {
+            from: ZERO_ID,
+            transfers: mints,
+            total,
+        })
+    }
+
+    fn check_minter(&self) -> Result<(), FTError> {
+        if !self.minters.contains(&msg::source()) {
+            return Err(FTError::NotMinter);
+        }
+        if self.paused {
+            return Err(FTError::Paused);
+        }
+        Ok(())
+    }
+
+    /// Returns the total supply after minting `amount` tokens.
+    fn supply_after_mint(&self, amount: u128) -> Result<u128, FTError> {
+        let total_supply = self
+            .total_supply
+            .checked_add(amount)
+            .ok_or(FTError::Overflow)?;
+        match self.max_supply {
+            Some(max_supply) if total_supply > max_supply => Err(FTError::MaxSupplyExceeded),
+            _ => Ok(total_supply),
+        }
+    }
+
+    /// Executed on receiving `FTAction::AddMinter`.
+    fn add_minter(&mut self, minter: &ActorId) -> Result<FTEvent, FTError> {
+        self.check_admin()?;
+        if minter == &ZERO_ID {
+            return Err(FTError::ZeroAddress);
+        }
+        if !self.minters.insert(*minter) {
+            return Err(FTError::AlreadyMinter);
+        }
+        Ok(FTEvent::MinterAdded(*minter))
+    }
+
+    /// Executed on receiving `FTAction::RemoveMinter`.
+    fn remove_minter(&mut self, minter: &ActorId) -> Result<FTEvent, FTError> {
+        self.check_admin()?;
+        if !self.minters.remove(minter) {
+            return Err(FTError::NotMinter);
+        }
+        Ok(FTEvent::MinterRemoved(*minter))
+    }
+
+    /// Executed on receiving `FTAction::TransferAdmin`.
+    fn transfer_admin(&mut self, new_admin: &ActorId) -> Result<FTEvent, FTError> {
+        self.check_admin()?;
+        if new_admin == &ZERO_ID {
+            return Err(FTError::ZeroAddress);
+        }
+        self.admin = *new_admin;
+        Ok(FTEvent::AdminTransferred {
+            from: msg::source(),
+            to: *new_admin,
+        })
+    }
+
+    /// Executed on receiving `FTAction::Pause` or `FTAction::Unpause`.
+    fn set_paused(&mut self, paused: bool) -> Result<FTEvent, FTError> {
+        self.check_admin()?;
+        if self.paused == paused {
+            return Err(if paused {
+                FTError::Paused
+            } else {
+                FTError::NotPaused
+            });
+        }
+        self.paused = paused;
+        Ok(if paused {
+            FTEvent::Paused
+        } else {
+            FTEvent::Unpaused
+        })
+    }
+
+    /// Executed on receiving `FTAction::Freeze`.
+    fn freeze(&mut self, account: &ActorId) -> Result<FTEvent, FTError> {
+        self.check_admin()?;
+        if !self.frozen.insert(*account) {
+            return Err(FTError::AccountFrozen(*account));
+        }
+        Ok(FTEvent::Frozen(*account))
+    }
+
+    /// Executed on receiving `FTAction::Unfreeze`.
+    fn unfreeze(&mut self, account: &ActorId) -> Result<FTEvent, FTError> {
+        self.check_admin()?;
+        if !self.frozen.remove(account) {
+            return Err(FTError::AccountNotFrozen(*account));
+        }
+        Ok(FTEvent::Unfrozen(*account))
+    }
+
+    /// Executed on receiving `FTAction::CreateVesting`.
+    /// Moves `amount` tokens from the admin account to the token program account
+    /// and vests them to `beneficiary`.
+    ///
+    /// Requirements:
+    /// * `msg::source()` must be the admin.
+    /// * `beneficiary` can't be the zero address or have a vesting already.
+    /// * `amount` can't be zero and `cliff` can't be longer than `duration`.
+    fn create_vesting(
+        &mut self,
+        beneficiary: &ActorId,
+        amount: u128,
+        start: u64,
+        cliff: u64,
+        duration: u64,
+    ) -> Result<FTEvent, FTError> {
+        self.check_admin()?;
+        self.check_can_send(&msg::source())?;
+        if self.vestings.contains_key(beneficiary) {
+            return Err(FTError::VestingExists);
+        }
+        if amount == 0 || cliff > duration {
+            return Err(FTError::InvalidVesting);
+        }
+        if beneficiary == &ZERO_ID {
+            return Err(FTError::ZeroAddress);
+        }
+        self.transfer_tokens(&msg::source(), &exec::program_id(), amount)?;
+        self.vestings.insert(
+            *beneficiary,
+            Vesting {
                 amount,
+                released: 0,
+                start,
+                cliff,
+                duration,
             },
-            0,
-        )
-        .unwrap();
+        );
+        Ok(FTEvent::VestingCreated {
+            beneficiary: *beneficiary,
+            amount,
+        })
     }
-    /// Executed on receiving `fungible-token-messages::BurnInput`.
-    fn burn(&mut self, amount: u128) {
-        if self.balances.get(&msg::source()).unwrap_or(&0) < &amount {
-            panic!("Amount exceeds account balance");
+
+    /// Executed on receiving `FTAction::Release`.
+    /// Transfers the vested tokens that haven't been released yet to `msg::source()`.
+    fn release(&mut self) -> Result<FTEvent, FTError> {
+        let beneficiary = msg::source();
+        self.check_can_send(&exec::program_id())?;
+        let mut vesting = *self.vestings.get(&beneficiary).ok_or(FTError::NoVesting)?;
+        let amount = releasable_amount(&vesting, exec::block_timestamp());
+        if amount == 0 {
+            return Err(FTError::NothingToRelease);
         }
-        self.balances
-            .entry(msg::source())
-            .and_modify(|balance| *balance -= amount);
+        self.transfer_tokens(&exec::program_id(), &beneficiary, amount)?;
+        vesting.released += amount;
+        if vesting.released == vesting.amount {
+            self.vestings.remove(&beneficiary);
+        } else {
+            self.vestings.insert(beneficiary, vesting);
+        }
+        Ok(FTEvent::VestingReleased {
+            beneficiary,
+            amount,
+        })
+    }
+
+    /// Executed on receiving `FTAction::RevokeVesting`.
+    /// Releases the vested tokens to `beneficiary` and returns the unvested ones to the admin.
+    fn revoke_vesting(&mut self, beneficiary: &ActorId) -> Result<FTEvent, FTError> {
+        self.check_admin()?;
+        self.check_can_send(&exec::program_id())?;
+        let vesting = *self.vestings.get(beneficiary).ok_or(FTError::NoVesting)?;
+        let vested = vested_amount(&vesting, exec::block_timestamp());
+        let released = vested - vesting.released;
+        let returned = vesting.amount - vested;
+        if released > 0 {
+            self.transfer_tokens(&exec::program_id(), beneficiary, released)?;
+        }
+        if returned > 0 {
+            self.transfer_tokens(&exec::program_id(), &msg::source(), returned)?;
+        }
+        self.vestings.remove(beneficiary);
+        Ok(FTEvent::VestingRevoked {
+            beneficiary: *beneficiary,
+            released,
+            returned,
+        })
+    }
+
+    /// Returns the vested and the unvested amounts of the vesting of `beneficiary` at `timestamp`.
+    fn vested_amounts(&self, beneficiary: &ActorId, timestamp: u64) -> (u128, u128) {
+        self.vestings
+            .get(beneficiary)
+            .map(|vesting| {
+                let vested = vested_amount(vesting, timestamp);
+                (vested, vesting.amount - vested)
+            })
+            .unwrap_or((0, 0))
+    }
+
+    fn check_admin(&self) -> Result<(), FTError> {
+        if msg::source() != self.admin {
+            return Err(FTError::NotAdmin);
+        }
+        Ok(())
+    }
+
+    /// Checks that the token is not paused and `from` account is not frozen.
+    fn check_can_send(&self, from: &ActorId) -> Result<(), FTError> {
+        if self.paused {
+            return Err(FTError::Paused);
+        }
+        if self.frozen.contains(from) {
+            return Err(FTError::AccountFrozen(*from));
+        }
+        Ok(())
+    }
+
+    /// Executed on receiving `FTAction::Burn`.
+    fn burn(&mut self, amount: u128) -> Result<FTEvent, FTError> {
+        self.check_can_send(&msg::source())?;
+        self.debit(&msg::source(), amount)?;
+        self.checkpoint_total_supply();
         self.total_supply -= amount;
+        Ok(FTEvent::Transfer {
+            from: msg::source(),
+            to: ZERO_ID,
+            amount,
+        })
+    }
 
-        msg::reply(
-            FTEvent::Transfer {
-                from: msg::source(),
-                to: ZERO_ID,
-                amount,
-            },
-            0,
-        )
-        .unwrap();
+    /// Executed on receiving `FTAction::Transfer`.
+    /// Transfers `amount` tokens from `msg::source()` account to `to` account.
+    fn transfer(&mut self, to: &ActorId, amount: u128) -> Result<FTEvent, FTError> {
+        self.check_can_send(&msg::source())?;
+        self.transfer_tokens(&msg::source(), to, amount)?;
+        Ok(FTEvent::Transfer {
+            from: msg::source(),
+            to: *to,
+            amount,
+        })
     }
-    /// Executed on receiving `fungible-token-messages::TransferInput` or `fungible-token-messages::TransferFromInput`.
-    /// Transfers `amount` tokens from `sender` account to `recipient` account.
-    fn transfer(&mut self, from: &ActorId, to: &ActorId, amount: u128) {
-        if from == &ZERO_ID || to == &ZERO_ID {
-            panic!("Zero addresses");
+
+    /// Executed on receiving `FTAction::TransferFrom`.
+    /// Transfers `amount` tokens from `from` account to `to` account
+    /// and decreases the allowance `from` granted to `msg::source()`.
+    fn transfer_from(
+        &mut self,
+        from: &ActorId,
+        to: &ActorId,
+        amount: u128,
+    ) -> Result<FTEvent, FTError> {
+        self.check_can_send(from)?;
+        let allowance = self.allowance(from, &msg::source());
+        if allowance < amount {
+            return Err(FTError::InsufficientAllowance);
+        }
+        self.transfer_tokens(from, to, amount)?;
+        self.set_allowance(from, &msg::source(), allowance - amount);
+        Ok(FTEvent::TransferFrom {
+            from: *from,
+            to: *to,
+            amount,
+            allowance: allowance - amount,
+        })
+    }
+
+    /// Executed on receiving `FTAction::BatchTransfer`.
+    /// Transfers tokens from `msg::source()` account to every recipient in `transfers`.
+    /// The whole batch is validated against the sender balance before any tokens move.
+    fn batch_transfer(&mut self, transfers: Vec<(ActorId, u128)>) -> Result<FTEvent, FTError> {
+        let from = msg::source();
+        self.check_can_send(&from)?;
+        let total = batch_total(&transfers)?;
+        self.debit(&from, total)?;
+        for (to, amount) in &transfers {
+            self.credit(to, *amount);
+        }
+        Ok(FTEvent::BatchTransfer {
+            from,
+            transfers,
+            total,
+        })
+    }
+
+    /// Executes `action` unless `msg::source()` has already sent a successful one with `tx_id`,
+    /// in which case the original reply is returned again.
+    /// The retry must be the same action, a different one with a used `tx_id` is rejected.
+    fn with_tx_id(
+        &mut self,
+        tx_id: Option<u64>,
+        action: impl FnOnce(&mut Self) -> Result<FTEvent, FTError>,
+    ) -> Result<FTEvent, FTError> {
+        let key = match tx_id {
+            Some(tx_id) => (msg::source(), tx_id),
+            None => return action(self),
         };
-        if !self.can_transfer(from, amount) {
-            panic!("Not allowed to transfer")
+        let hash = sp_core_hashing::blake2_256(&msg::load_bytes());
+        if let Some((action_hash, reply)) = self.transactions.get(&key) {
+            if action_hash != &hash {
+                return Err(FTError::TxIdReused);
+            }
+            return Ok(reply.clone());
+        }
+        let reply = action(self)?;
+        if self.transaction_queue.len() >= MAX_TRANSACTIONS {
+            if let Some(oldest) = self.transaction_queue.pop_front() {
+                self.transactions.remove(&oldest);
+            }
         }
+        self.transaction_queue.push_back(key);
+        self.transactions.insert(key, (hash, reply.clone()));
+        Ok(reply)
+    }
+
+    /// Moves `amount` tokens from `from` account to `to` account.
+    fn transfer_tokens(
+        &mut self,
+        from: &ActorId,
+        to: &ActorId,
+        amount: u128,
+    ) -> Result<(), FTError> {
+        if from == &ZERO_ID || to == &ZERO_ID {
+            return Err(FTError::ZeroAddress);
+        };
+        self.debit(from, amount)?;
+        self.credit(to, amount);
+        Ok(())
+    }
+
+    fn debit(&mut self, from: &ActorId, amount: u128) -> Result<(), FTError> {
         if self.balances.get(from).unwrap_or(&0) < &amount {
-            panic!("Amount exceeds account balance");
+            return Err(FTError::InsufficientBalance);
         }
+        self.checkpoint_balance(from);
         self.balances
             .entry(*from)
             .and_modify(|balance| *balance -= amount);
+        Ok(())
+    }
+
+    fn credit(&mut self, to: &ActorId, amount: u128) {
+        self.checkpoint_balance(to);
         self.balances
             .entry(*to)
             .and_modify(|balance| *balance += amount)
             .or_insert(amount);
-        msg::reply(
-            FTEvent::Transfer {
-                from: *from,
-                to: *to,
-                amount,
-            },
-            0,
+    }
+
+    /// Executed on receiving `FTAction::Snapshot`.
+    /// Starts a new snapshot and replies with its ID.
+    ///
+    /// Requirements:
+    /// * `msg::source()` must be the admin or the snapshotter.
+    fn snapshot(&mut self) -> Result<FTEvent, FTError> {
+        if msg::source() != self.admin && Some(msg::source()) != self.snapshotter {
+            return Err(FTError::NotSnapshotter);
+        }
+        self.snapshot_id += 1;
+        Ok(FTEvent::Snapshot(self.snapshot_id))
+    }
+
+    /// Executed on receiving `FTAction::SetSnapshotter`.
+    fn set_snapshotter(&mut self, snapshotter: Option<ActorId>) -> Result<FTEvent, FTError> {
+        self.check_admin()?;
+        self.snapshotter = snapshotter;
+        Ok(FTEvent::SnapshotterSet(snapshotter))
+    }
+
+    /// Records the balance of `account` for the current snapshot before it changes.
+    fn checkpoint_balance(&mut self, account: &ActorId) {
+        if self.snapshot_id == 0 {
+            return;
+        }
+        let balance = *self.balances.get(account).unwrap_or(&0);
+        checkpoint(
+            self.balance_checkpoints.entry(*account).or_default(),
+            self.snapshot_id,
+            balance,
+        );
+    }
+
+    /// Records the total supply for the current snapshot before it changes.
+    fn checkpoint_total_supply(&mut self) {
+        if self.snapshot_id == 0 {
+            return;
+        }
+        checkpoint(
+            &mut self.total_supply_checkpoints,
+            self.snapshot_id,
+            self.total_supply,
+        );
+    }
+
+    fn balance_of_at(&self, account: &ActorId, snapshot_id: u64) -> u128 {
+        self.check_snapshot_id(snapshot_id);
+        let balance = *self.balances.get(account).unwrap_or(&0);
+        self.balance_checkpoints
+            .get(account)
+            .map(|checkpoints| value_at(checkpoints, snapshot_id, balance))
+            .unwrap_or(balance)
+    }
+
+    fn total_supply_at(&self, snapshot_id: u64) -> u128 {
+        self.check_snapshot_id(snapshot_id);
+        value_at(
+            &self.total_supply_checkpoints,
+            snapshot_id,
+            self.total_supply,
         )
-        .unwrap();
     }
 
-    /// Executed on receiving `fungible-token-messages::ApproveInput`.
-    fn approve(&mut self, to: &ActorId, amount: u128) {
+    fn check_snapshot_id(&self, snapshot_id: u64) {
+        if snapshot_id == 0 || snapshot_id > self.snapshot_id {
+            panic!("Snapshot {snapshot_id} doesn't exist");
+        }
+    }
+
+    /// Executed on receiving `FTAction::IncreaseAllowance`.
+    fn increase_allowance(&mut self, to: &ActorId, amount: u128) -> Result<FTEvent, FTError> {
+        let allowance = self
+            .allowance(&msg::source(), to)
+            .checked_add(amount)
+            .ok_or(FTError::Overflow)?;
+        self.approve(to, allowance)
+    }
+
+    /// Executed on receiving `FTAction::DecreaseAllowance`.
+    fn decrease_allowance(&mut self, to: &ActorId, amount: u128) -> Result<FTEvent, FTError> {
+        let allowance = self
+            .allowance(&msg::source(), to)
+            .checked_sub(amount)
+            .ok_or(FTError::InsufficientAllowance)?;
+        self.approve(to, allowance)
+    }
+
+    /// Executed on receiving `FTAction::Approve`.
+    /// Sets the allowance of `to` over the tokens of `msg::source()` to `amount`.
+    fn approve(&mut self, to: &ActorId, amount: u128) -> Result<FTEvent, FTError> {
         if to == &ZERO_ID {
-            panic!("Approve to zero address");
+            return Err(FTError::ZeroAddress);
         }
-        self.allowances
-            .entry(msg::source())
-            .or_default()
-            .insert(*to, amount);
-        msg::reply(
-            FTEvent::Approve {
-                from: msg::source(),
-                to: *to,
-                amount,
-            },
-            0,
-        )
-        .unwrap();
+        self.set_allowance(&msg::source(), to, amount);
+        Ok(FTEvent::Approve {
+            from: msg::source(),
+            to: *to,
+            amount,
+        })
     }
 
-    fn can_transfer(&mut self, from: &ActorId, amount: u128) -> bool {
-        if from == &msg::source()
-            || from == &exec::origin()
-            || self.balances.get(&msg::source()).unwrap_or(&0) >= &amount
-        {
-            return true;
+    /// Executed on receiving `FTAction::Permit`.
+    /// Sets the allowance of `spender` over the tokens of `owner` to `amount`
+    /// if `owner` signed the permit.
+    ///
+    /// Requirements:
+    /// * `deadline` must not have passed.
+    /// * `nonce` must be the current nonce of `owner`.
+    /// * `signature` must be a valid signature of `owner` over the [`PermitPayload`].
+    fn permit(
+        &mut self,
+        owner: &ActorId,
+        spender: &ActorId,
+        amount: u128,
+        deadline: u64,
+        nonce: u64,
+        signature: &[u8; 64],
+    ) -> Result<FTEvent, FTError> {
+        if exec::block_timestamp() > deadline {
+            return Err(FTError::PermitExpired);
         }
-        if let Some(allowed_amount) = self
-            .allowances
-            .get(from)
-            .and_then(|m| m.get(&msg::source()))
-        {
-            if allowed_amount >= &amount {
-                self.allowances.entry(*from).and_modify(|m| {
-                    m.entry(msg::source()).and_modify(|a| *a -= amount);
-                });
-                return true;
-            }
+        if spender == &ZERO_ID {
+            return Err(FTError::ZeroAddress);
         }
-        false
+        if nonce != self.nonce(owner) {
+            return Err(FTError::InvalidNonce);
+        }
+        let payload = PermitPayload {
+            token: exec::program_id(),
+            owner: *owner,
+            spender: *spender,
+            amount,
+            deadline,
+            nonce,
+        };
+        verify_signature(owner, &payload.encode(), signature)?;
+        self.nonces.insert(*owner, nonce + 1);
+        self.set_allowance(owner, spender, amount);
+        Ok(FTEvent::Approve {
+            from: *owner,
+            to: *spender,
+            amount,
+        })
+    }
+
+    fn nonce(&self, owner: &ActorId) -> u64 {
+        *self.nonces.get(owner).unwrap_or(&0)
     }
+
+    fn allowance(&self, owner: &ActorId, spender: &ActorId) -> u128 {
+        *self
+            .allowances
+            .get(owner)
+            .and_then(|m| m.get(spender))
+            .unwrap_or(&0)
+    }
+
+    fn set_allowance(&mut self, owner: &ActorId, spender: &ActorId, amount: u128) {
+        self.allowances
+            .entry(*owner)
+            .or_default()
+            .insert(*spender, amount);
+    }
+}
+
+/// Records `value` as the value at `snapshot_id`
+/// unless it has already been recorded for this snapshot.
+fn checkpoint(checkpoints: &mut Vec<(u64, u128)>, snapshot_id: u64, value: u128) {
+    if checkpoints.last().map(|(id, _)| *id) < Some(snapshot_id) {
+        checkpoints.push((snapshot_id, value));
+    }
+}
+
+/// Returns the value at `snapshot_id`.
+/// Every checkpoint holds the value as it was when its snapshot was taken,
+/// so the first checkpoint not older than `snapshot_id` has the answer,
+/// and if there is none the value hasn't changed since.
+fn value_at(checkpoints: &[(u64, u128)], snapshot_id: u64, current: u128) -> u128 {
+    let index = checkpoints.partition_point(|(id, _)| *id < snapshot_id);
+    checkpoints
+        .get(index)
+        .map(|(_, value)| *value)
+        .unwrap_or(current)
+}
+
+/// Returns the amount of tokens vested at `timestamp` that haven't been released yet.
+fn releasable_amount(vesting: &Vesting, timestamp: u64) -> u128 {
+    vested_amount(vesting, timestamp) - vesting.released
+}
+
+/// Returns the amount of tokens vested at `timestamp`, including the released ones.
+fn vested_amount(vesting: &Vesting, timestamp: u64) -> u128 {
+    if timestamp < vesting.start {
+        return 0;
+    }
+    let elapsed = timestamp - vesting.start;
+    if elapsed < vesting.cliff {
+        return 0;
+    }
+    if elapsed >= vesting.duration {
+        return vesting.amount;
+    }
+    // split the amount so that multiplying by `elapsed` can't overflow
+    let (elapsed, duration) = (elapsed as u128, vesting.duration as u128);
+    vesting.amount / duration * elapsed + vesting.amount % duration * elapsed / duration
+}
+
+/// Checks that `signature` is the sr25519 signature of `signer` over `message`.
+fn verify_signature(signer: &ActorId, message: &[u8], signature: &[u8; 64]) -> Result<(), FTError> {
+    let public_key =
+        PublicKey::from_bytes(signer.as_ref()).map_err(|_| FTError::InvalidSignature)?;
+    let signature = Signature::from_bytes(signature).map_err(|_| FTError::InvalidSignature)?;
+    public_key
+        .verify_simple(SIGNING_CONTEXT, message, &signature)
+        .map_err(|_| FTError::InvalidSignature)
+}
+
+/// Sums the amounts of a batch, checking that it is not empty
+/// and doesn't contain the zero address.
+fn batch_total(batch: &[(ActorId, u128)]) -> Result<u128, FTError> {
+    if batch.is_empty() {
+        return Err(FTError::EmptyBatch);
+    }
+    batch.iter().try_fold(0u128, |total, (to, amount)| {
+        if to == &ZERO_ID {
+            return Err(FTError::ZeroAddress);
+        }
+        total.checked_add(*amount).ok_or(FTError::Overflow)
+    })
 }
 
 gstd::metadata! {
@@ -151,27 +695,57 @@ gstd::metadata! {
 pub unsafe extern "C" fn handle() {
     let action: FTAction = msg::load().expect("Could not load Action");
     let ft: &mut FungibleToken = FUNGIBLE_TOKEN.get_or_insert(FungibleToken::default());
-    match action {
-        FTAction::Mint(amount) => {
-            ft.mint(amount);
-        }
-        FTAction::Burn(amount) => {
-            ft.burn(amount);
-        }
-        FTAction::Transfer { from, to, amount } => {
-            ft.transfer(&from, &to, amount);
-        }
-        FTAction::Approve { to, amount } => {
-            ft.approve(&to, amount);
-        }
-        FTAction::TotalSupply => {
-            msg::reply(FTEvent::TotalSupply(ft.total_supply), 0).unwrap();
+    let result = match action {
+        FTAction::Mint { to, amount } => ft.mint(&to, amount),
+        FTAction::BatchMint(mints) => ft.batch_mint(mints),
+        FTAction::Burn(amount) => ft.burn(amount),
+        FTAction::Transfer { to, amount, tx_id } => {
+            ft.with_tx_id(tx_id, |ft| ft.transfer(&to, amount))
         }
+        FTAction::TransferFrom {
+            from,
+            to,
+            amount,
+            tx_id,
+        } => ft.with_tx_id(tx_id, |ft| ft.transfer_from(&from, &to, amount)),
+        FTAction::BatchTransfer(transfers) => ft.batch_transfer(transfers),
+        FTAction::Approve { to, amount } => ft.approve(&to, amount),
+        FTAction::IncreaseAllowance { to, amount } => ft.increase_allowance(&to, amount),
+        FTAction::DecreaseAllowance { to, amount } => ft.decrease_allowance(&to, amount),
+        FTAction::Permit {
+            owner,
+            spender,
+            amount,
+            deadline,
+            nonce,
+            signature,
+        } => ft.permit(&owner, &spender, amount, deadline, nonce, &signature),
+        FTAction::TotalSupply => Ok(FTEvent::TotalSupply(ft.total_supply)),
         FTAction::BalanceOf(account) => {
             let balance = ft.balances.get(&account).unwrap_or(&0);
-            msg::reply(FTEvent::Balance(*balance), 0).unwrap();
+            Ok(FTEvent::Balance(*balance))
         }
-    }
+        FTAction::AddMinter(minter) => ft.add_minter(&minter),
+        FTAction::RemoveMinter(minter) => ft.remove_minter(&minter),
+        FTAction::TransferAdmin(new_admin) => ft.transfer_admin(&new_admin),
+        FTAction::Snapshot => ft.snapshot(),
+        FTAction::SetSnapshotter(snapshotter) => ft.set_snapshotter(snapshotter),
+        FTAction::Pause => ft.set_paused(true),
+        FTAction::Unpause => ft.set_paused(false),
+        FTAction::Freeze(account) => ft.freeze(&account),
+        FTAction::Unfreeze(account) => ft.unfreeze(&account),
+        FTAction::CreateVesting {
+            beneficiary,
+            amount,
+            start,
+            cliff,
+            duration,
+        } => ft.create_vesting(&beneficiary, amount, start, cliff, duration),
+        FTAction::Release => ft.release(),
+        FTAction::RevokeVesting(beneficiary) => ft.revoke_vesting(&beneficiary),
+    };
+    let reply = result.unwrap_or_else(FTEvent::Err);
+    msg::reply(reply, 0).unwrap();
 }
 
 #[no_mangle]
@@ -180,6 +754,10 @@ pub unsafe extern "C" fn init() {
     let ft = FungibleToken {
         name: config.name,
         symbol: config.symbol,
+        decimals: config.decimals,
+        admin: config.admin,
+        minters: config.minters.into_iter().collect(),
+        max_supply: config.max_supply,
         ..FungibleToken::default()
     };
     FUNGIBLE_TOKEN = Some(ft);
@@ -191,13 +769,50 @@ pub unsafe extern "C" fn meta_state() -> *mut [i32; 2] {
     let ft: &mut FungibleToken = FUNGIBLE_TOKEN.get_or_insert(FungibleToken::default());
     let encoded = match query {
         State::Name => StateReply::Name(ft.name.clone()).encode(),
-        State::Symbol => StateReply::Name(ft.symbol.clone()).encode(),
-        State::Decimals => StateReply::Decimals(18).encode(),
+        State::Symbol => StateReply::Symbol(ft.symbol.clone()).encode(),
+        State::Decimals => StateReply::Decimals(ft.decimals).encode(),
         State::TotalSupply => StateReply::TotalSupply(ft.total_supply).encode(),
         State::BalanceOf(account) => {
             let balance = ft.balances.get(&account).unwrap_or(&0);
             StateReply::Balance(*balance).encode()
         }
+        State::Allowance { owner, spender } => {
+            StateReply::Allowance(ft.allowance(&owner, &spender)).encode()
+        }
+        State::Metadata => StateReply::Metadata {
+            name: ft.name.clone(),
+            symbol: ft.symbol.clone(),
+            decimals: ft.decimals,
+            total_supply: ft.total_supply,
+        }
+        .encode(),
+        State::Holders { offset, limit } => {
+            let holders = ft
+                .balances
+                .iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .map(|(account, balance)| (*account, *balance))
+                .collect();
+            StateReply::Holders(holders).encode()
+        }
+        State::BalanceOfAt {
+            account,
+            snapshot_id,
+        } => StateReply::Balance(ft.balance_of_at(&account, snapshot_id)).encode(),
+        State::TotalSupplyAt(snapshot_id) => {
+            StateReply::TotalSupply(ft.total_supply_at(snapshot_id)).encode()
+        }
+        State::IsPaused => StateReply::IsPaused(ft.paused).encode(),
+        State::IsFrozen(account) => StateReply::IsFrozen(ft.frozen.contains(&account)).encode(),
+        State::Nonce(account) => StateReply::Nonce(ft.nonce(&account)).encode(),
+        State::Vesting(beneficiary) => {
+            StateReply::Vesting(ft.vestings.get(&beneficiary).copied()).encode()
+        }
+        State::VestedAmount(beneficiary) => {
+            let (vested, unvested) = ft.vested_amounts(&beneficiary, exec::block_timestamp());
+            StateReply::VestedAmount { vested, unvested }.encode()
+        }
     };
     let result = gstd::macros::util::to_wasm_ptr(&(encoded[..]));
     core::mem::forget(encoded);
@@ -1,7 +1,9 @@
+use super::{checkpoint, releasable_amount, value_at, vested_amount, FungibleToken};
 use codec::Encode;
 use ft_io::*;
-use gstd::String;
+use gstd::{vec, ActorId, String, Vec};
 use gtest::{Program, System};
+use schnorrkel::{ExpansionMode, Keypair, MiniSecretKey};
 const USERS: &'static [u64] = &[3, 4, 5];
 
 fn init_with_mint(sys: &System) {
@@ -14,12 +16,22 @@ fn init_with_mint(sys: &System) {
         InitConfig {
             name: String::from("MyToken"),
             symbol: String::from("MTK"),
+            decimals: 18,
+            admin: USERS[0].into(),
+            minters: vec![USERS[0].into()],
+            max_supply: None,
         },
     );
 
     assert!(res.log().is_empty());
 
-    let res = ft.send(USERS[0], FTAction::Mint(1000000));
+    let res = ft.send(
+        USERS[0],
+        FTAction::Mint {
+            to: USERS[0].into(),
+            amount: 1000000,
+        },
+    );
     assert!(res.contains(&(
         USERS[0],
         FTEvent::Transfer {
@@ -67,7 +79,10 @@ fn burn_failures() {
     let ft = sys.get_program(1);
     // must fail since the amount > the user balance
     let res = ft.send(USERS[0], FTAction::Burn(1000001));
-    assert!(res.main_failed());
+    assert!(res.contains(&(
+        USERS[0],
+        FTEvent::Err(FTError::InsufficientBalance).encode()
+    )));
 }
 
 #[test]
@@ -78,9 +93,9 @@ fn transfer() {
     let res = ft.send(
         USERS[0],
         FTAction::Transfer {
-            from: USERS[0].into(),
             to: USERS[1].into(),
             amount: 500,
+            tx_id: None,
         },
     );
 
@@ -101,6 +116,108 @@ fn transfer() {
     assert!(res.contains(&(USERS[0], FTEvent::Balance(500).encode())));
 }
 
+#[test]
+fn transfer_with_tx_id() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let ft = sys.get_program(1);
+    let transfer = || FTAction::Transfer {
+        to: USERS[1].into(),
+        amount: 500,
+        tx_id: Some(1),
+    };
+    let event = FTEvent::Transfer {
+        from: USERS[0].into(),
+        to: USERS[1].into(),
+        amount: 500,
+    };
+
+    let res = ft.send(USERS[0], transfer());
+    assert!(res.contains(&(USERS[0], event.encode())));
+
+    // a retry gets the original reply without transferring again
+    let res = ft.send(USERS[0], transfer());
+    assert!(res.contains(&(USERS[0], event.encode())));
+    let res = ft.send(USERS[0], FTAction::BalanceOf(USERS[1].into()));
+    assert!(res.contains(&(USERS[0], FTEvent::Balance(500).encode())));
+
+    // must fail since the transaction ID is used for a different transfer
+    let res = ft.send(
+        USERS[0],
+        FTAction::Transfer {
+            to: USERS[1].into(),
+            amount: 600,
+            tx_id: Some(1),
+        },
+    );
+    assert!(res.contains(&(USERS[0], FTEvent::Err(FTError::TxIdReused).encode())));
+    let res = ft.send(USERS[0], FTAction::BalanceOf(USERS[1].into()));
+    assert!(res.contains(&(USERS[0], FTEvent::Balance(500).encode())));
+
+    // transaction IDs are tracked per sender
+    let res = ft.send(
+        USERS[1],
+        FTAction::Transfer {
+            to: USERS[2].into(),
+            amount: 100,
+            tx_id: Some(1),
+        },
+    );
+    assert!(res.contains(&(
+        USERS[1],
+        FTEvent::Transfer {
+            from: USERS[1].into(),
+            to: USERS[2].into(),
+            amount: 100,
+        }
+        .encode()
+    )));
+
+    // failed transfers aren't remembered and can be retried
+    let res = ft.send(
+        USERS[1],
+        FTAction::TransferFrom {
+            from: USERS[0].into(),
+            to: USERS[1].into(),
+            amount: 200,
+            tx_id: Some(2),
+        },
+    );
+    assert!(res.contains(&(
+        USERS[1],
+        FTEvent::Err(FTError::InsufficientAllowance).encode()
+    )));
+    let res = ft.send(
+        USERS[0],
+        FTAction::Approve {
+            to: USERS[1].into(),
+            amount: 200,
+        },
+    );
+    assert!(!res.main_failed());
+    let res = ft.send(
+        USERS[1],
+        FTAction::TransferFrom {
+            from: USERS[0].into(),
+            to: USERS[1].into(),
+            amount: 200,
+            tx_id: Some(2),
+        },
+    );
+    assert!(res.contains(&(
+        USERS[1],
+        FTEvent::TransferFrom {
+            from: USERS[0].into(),
+            to: USERS[1].into(),
+            amount: 200,
+            allowance: 0,
+        }
+        .encode()
+    )));
+    let res = ft.send(USERS[0], FTAction::BalanceOf(USERS[0].into()));
+    assert!(res.contains(&(USERS[0], FTEvent::Balance(999300).encode())));
+}
+
 #[test]
 fn transfer_failures() {
     let sys = System::new();
@@ -110,23 +227,26 @@ fn transfer_failures() {
     let res = ft.send(
         USERS[0],
         FTAction::Transfer {
-            from: USERS[0].into(),
             to: USERS[1].into(),
             amount: 2000000,
+            tx_id: None,
         },
     );
-    assert!(res.main_failed());
+    assert!(res.contains(&(
+        USERS[0],
+        FTEvent::Err(FTError::InsufficientBalance).encode()
+    )));
 
     //must fail transfer to zero address
     let res = ft.send(
-        USERS[2],
+        USERS[0],
         FTAction::Transfer {
-            from: USERS[0].into(),
             to: 0.into(),
             amount: 100,
+            tx_id: None,
         },
     );
-    assert!(res.main_failed());
+    assert!(res.contains(&(USERS[0], FTEvent::Err(FTError::ZeroAddress).encode())));
 }
 
 #[test]
@@ -154,18 +274,20 @@ fn approve_and_transfer() {
 
     let res = ft.send(
         USERS[1],
-        FTAction::Transfer {
+        FTAction::TransferFrom {
             from: USERS[0].into(),
             to: USERS[2].into(),
             amount: 200,
+            tx_id: None,
         },
     );
     assert!(res.contains(&(
         USERS[1],
-        FTEvent::Transfer {
+        FTEvent::TransferFrom {
             from: USERS[0].into(),
             to: USERS[2].into(),
             amount: 200,
+            allowance: 300,
         }
         .encode()
     )));
@@ -179,11 +301,909 @@ fn approve_and_transfer() {
     // must fail since not enough allowance
     let res = ft.send(
         USERS[1],
+        FTAction::TransferFrom {
+            from: USERS[0].into(),
+            to: USERS[2].into(),
+            amount: 800,
+            tx_id: None,
+        },
+    );
+    assert!(res.contains(&(
+        USERS[1],
+        FTEvent::Err(FTError::InsufficientAllowance).encode()
+    )));
+}
+
+#[test]
+fn transfer_from_failures() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let ft = sys.get_program(1);
+
+    // must fail since `USERS[1]` has no allowance
+    let res = ft.send(
+        USERS[1],
+        FTAction::TransferFrom {
+            from: USERS[0].into(),
+            to: USERS[1].into(),
+            amount: 100,
+            tx_id: None,
+        },
+    );
+    assert!(res.contains(&(
+        USERS[1],
+        FTEvent::Err(FTError::InsufficientAllowance).encode()
+    )));
+
+    // must fail since the spender's own balance doesn't grant an allowance
+    let res = ft.send(
+        USERS[0],
         FTAction::Transfer {
+            to: USERS[1].into(),
+            amount: 1000,
+            tx_id: None,
+        },
+    );
+    assert!(!res.main_failed());
+    let res = ft.send(
+        USERS[1],
+        FTAction::TransferFrom {
             from: USERS[0].into(),
             to: USERS[2].into(),
+            amount: 100,
+            tx_id: None,
+        },
+    );
+    assert!(res.contains(&(
+        USERS[1],
+        FTEvent::Err(FTError::InsufficientAllowance).encode()
+    )));
+}
+
+#[test]
+fn increase_and_decrease_allowance() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let ft = sys.get_program(1);
+
+    let res = ft.send(
+        USERS[0],
+        FTAction::IncreaseAllowance {
+            to: USERS[1].into(),
+            amount: 500,
+        },
+    );
+    assert!(res.contains(&(
+        USERS[0],
+        FTEvent::Approve {
+            from: USERS[0].into(),
+            to: USERS[1].into(),
+            amount: 500,
+        }
+        .encode()
+    )));
+
+    let res = ft.send(
+        USERS[0],
+        FTAction::IncreaseAllowance {
+            to: USERS[1].into(),
+            amount: 300,
+        },
+    );
+    assert!(res.contains(&(
+        USERS[0],
+        FTEvent::Approve {
+            from: USERS[0].into(),
+            to: USERS[1].into(),
             amount: 800,
+        }
+        .encode()
+    )));
+
+    let res = ft.send(
+        USERS[0],
+        FTAction::DecreaseAllowance {
+            to: USERS[1].into(),
+            amount: 600,
+        },
+    );
+    assert!(res.contains(&(
+        USERS[0],
+        FTEvent::Approve {
+            from: USERS[0].into(),
+            to: USERS[1].into(),
+            amount: 200,
+        }
+        .encode()
+    )));
+
+    // must fail since the allowance can't go below zero
+    let res = ft.send(
+        USERS[0],
+        FTAction::DecreaseAllowance {
+            to: USERS[1].into(),
+            amount: 201,
+        },
+    );
+    assert!(res.contains(&(
+        USERS[0],
+        FTEvent::Err(FTError::InsufficientAllowance).encode()
+    )));
+
+    // must fail since not enough allowance
+    let res = ft.send(
+        USERS[1],
+        FTAction::TransferFrom {
+            from: USERS[0].into(),
+            to: USERS[2].into(),
+            amount: 201,
+            tx_id: None,
+        },
+    );
+    assert!(res.contains(&(
+        USERS[1],
+        FTEvent::Err(FTError::InsufficientAllowance).encode()
+    )));
+}
+
+#[test]
+fn mint_failures() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let ft = sys.get_program(1);
+
+    // must fail since `USERS[1]` is not a minter
+    let res = ft.send(
+        USERS[1],
+        FTAction::Mint {
+            to: USERS[1].into(),
+            amount: 1000,
+        },
+    );
+    assert!(res.contains(&(USERS[1], FTEvent::Err(FTError::NotMinter).encode())));
+
+    // must fail since minting to the zero address
+    let res = ft.send(
+        USERS[0],
+        FTAction::Mint {
+            to: 0.into(),
+            amount: 1000,
+        },
+    );
+    assert!(res.contains(&(USERS[0], FTEvent::Err(FTError::ZeroAddress).encode())));
+}
+
+#[test]
+fn max_supply() {
+    let sys = System::new();
+    sys.init_logger();
+    let ft = Program::current(&sys);
+
+    let res = ft.send(
+        USERS[0],
+        InitConfig {
+            name: String::from("MyToken"),
+            symbol: String::from("MTK"),
+            decimals: 18,
+            admin: USERS[0].into(),
+            minters: vec![USERS[0].into()],
+            max_supply: Some(1000),
+        },
+    );
+    assert!(res.log().is_empty());
+
+    let res = ft.send(
+        USERS[0],
+        FTAction::Mint {
+            to: USERS[1].into(),
+            amount: 1000,
+        },
+    );
+    assert!(!res.main_failed());
+
+    // must fail since the total supply would exceed the max supply
+    let res = ft.send(
+        USERS[0],
+        FTAction::Mint {
+            to: USERS[1].into(),
+            amount: 1,
+        },
+    );
+    assert!(res.contains(&(USERS[0], FTEvent::Err(FTError::MaxSupplyExceeded).encode())));
+
+    // burnt tokens free up the supply
+    let res = ft.send(USERS[1], FTAction::Burn(10));
+    assert!(!res.main_failed());
+    let res = ft.send(
+        USERS[0],
+        FTAction::Mint {
+            to: USERS[1].into(),
+            amount: 10,
         },
     );
-    assert!(res.main_failed());
+    assert!(res.contains(&(
+        USERS[0],
+        FTEvent::Transfer {
+            from: 0.into(),
+            to: USERS[1].into(),
+            amount: 10,
+        }
+        .encode()
+    )));
+}
+
+#[test]
+fn manage_minters() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let ft = sys.get_program(1);
+
+    // must fail since only admin can add minters
+    let res = ft.send(USERS[1], FTAction::AddMinter(USERS[1].into()));
+    assert!(res.contains(&(USERS[1], FTEvent::Err(FTError::NotAdmin).encode())));
+
+    let res = ft.send(USERS[0], FTAction::AddMinter(USERS[1].into()));
+    assert!(res.contains(&(USERS[0], FTEvent::MinterAdded(USERS[1].into()).encode())));
+    let res = ft.send(
+        USERS[1],
+        FTAction::Mint {
+            to: USERS[2].into(),
+            amount: 100,
+        },
+    );
+    assert!(res.contains(&(
+        USERS[1],
+        FTEvent::Transfer {
+            from: 0.into(),
+            to: USERS[2].into(),
+            amount: 100,
+        }
+        .encode()
+    )));
+
+    let res = ft.send(USERS[0], FTAction::RemoveMinter(USERS[1].into()));
+    assert!(res.contains(&(USERS[0], FTEvent::MinterRemoved(USERS[1].into()).encode())));
+    let res = ft.send(
+        USERS[1],
+        FTAction::Mint {
+            to: USERS[2].into(),
+            amount: 100,
+        },
+    );
+    assert!(res.contains(&(USERS[1], FTEvent::Err(FTError::NotMinter).encode())));
+
+    let res = ft.send(USERS[0], FTAction::TransferAdmin(USERS[2].into()));
+    assert!(res.contains(&(
+        USERS[0],
+        FTEvent::AdminTransferred {
+            from: USERS[0].into(),
+            to: USERS[2].into(),
+        }
+        .encode()
+    )));
+
+    // must fail since `USERS[0]` is no longer admin
+    let res = ft.send(USERS[0], FTAction::AddMinter(USERS[1].into()));
+    assert!(res.contains(&(USERS[0], FTEvent::Err(FTError::NotAdmin).encode())));
+    let res = ft.send(USERS[2], FTAction::AddMinter(USERS[1].into()));
+    assert!(!res.main_failed());
+}
+
+#[test]
+fn pause() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let ft = sys.get_program(1);
+
+    // must fail since only admin can pause the token
+    let res = ft.send(USERS[1], FTAction::Pause);
+    assert!(res.contains(&(USERS[1], FTEvent::Err(FTError::NotAdmin).encode())));
+
+    let res = ft.send(USERS[0], FTAction::Pause);
+    assert!(res.contains(&(USERS[0], FTEvent::Paused.encode())));
+
+    let res = ft.send(
+        USERS[0],
+        FTAction::Transfer {
+            to: USERS[1].into(),
+            amount: 100,
+            tx_id: None,
+        },
+    );
+    assert!(res.contains(&(USERS[0], FTEvent::Err(FTError::Paused).encode())));
+    let res = ft.send(
+        USERS[0],
+        FTAction::Mint {
+            to: USERS[1].into(),
+            amount: 100,
+        },
+    );
+    assert!(res.contains(&(USERS[0], FTEvent::Err(FTError::Paused).encode())));
+    let res = ft.send(USERS[0], FTAction::Burn(100));
+    assert!(res.contains(&(USERS[0], FTEvent::Err(FTError::Paused).encode())));
+
+    // check that nothing has changed
+    let res = ft.send(USERS[0], FTAction::TotalSupply);
+    assert!(res.contains(&(USERS[0], FTEvent::TotalSupply(1000000).encode())));
+    let res = ft.send(USERS[0], FTAction::BalanceOf(USERS[0].into()));
+    assert!(res.contains(&(USERS[0], FTEvent::Balance(1000000).encode())));
+
+    let res = ft.send(USERS[0], FTAction::Unpause);
+    assert!(res.contains(&(USERS[0], FTEvent::Unpaused.encode())));
+    let res = ft.send(
+        USERS[0],
+        FTAction::Transfer {
+            to: USERS[1].into(),
+            amount: 100,
+            tx_id: None,
+        },
+    );
+    assert!(res.contains(&(
+        USERS[0],
+        FTEvent::Transfer {
+            from: USERS[0].into(),
+            to: USERS[1].into(),
+            amount: 100,
+        }
+        .encode()
+    )));
+}
+
+#[test]
+fn freeze() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let ft = sys.get_program(1);
+
+    let res = ft.send(
+        USERS[0],
+        FTAction::Approve {
+            to: USERS[1].into(),
+            amount: 500,
+        },
+    );
+    assert!(!res.main_failed());
+
+    // must fail since only admin can freeze accounts
+    let res = ft.send(USERS[1], FTAction::Freeze(USERS[0].into()));
+    assert!(res.contains(&(USERS[1], FTEvent::Err(FTError::NotAdmin).encode())));
+
+    let res = ft.send(USERS[0], FTAction::Freeze(USERS[0].into()));
+    assert!(res.contains(&(USERS[0], FTEvent::Frozen(USERS[0].into()).encode())));
+
+    let frozen = FTEvent::Err(FTError::AccountFrozen(USERS[0].into())).encode();
+    let res = ft.send(
+        USERS[0],
+        FTAction::Transfer {
+            to: USERS[1].into(),
+            amount: 100,
+            tx_id: None,
+        },
+    );
+    assert!(res.contains(&(USERS[0], frozen.clone())));
+    let res = ft.send(
+        USERS[1],
+        FTAction::TransferFrom {
+            from: USERS[0].into(),
+            to: USERS[2].into(),
+            amount: 100,
+            tx_id: None,
+        },
+    );
+    assert!(res.contains(&(USERS[1], frozen)));
+
+    // a frozen account can still receive tokens
+    let res = ft.send(
+        USERS[0],
+        FTAction::Mint {
+            to: USERS[0].into(),
+            amount: 100,
+        },
+    );
+    assert!(!res.main_failed());
+
+    let res = ft.send(USERS[0], FTAction::Unfreeze(USERS[0].into()));
+    assert!(res.contains(&(USERS[0], FTEvent::Unfrozen(USERS[0].into()).encode())));
+    let res = ft.send(
+        USERS[1],
+        FTAction::TransferFrom {
+            from: USERS[0].into(),
+            to: USERS[2].into(),
+            amount: 100,
+            tx_id: None,
+        },
+    );
+    assert!(res.contains(&(
+        USERS[1],
+        FTEvent::TransferFrom {
+            from: USERS[0].into(),
+            to: USERS[2].into(),
+            amount: 100,
+            allowance: 400,
+        }
+        .encode()
+    )));
+}
+
+#[test]
+fn batch_transfer() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let ft = sys.get_program(1);
+
+    let transfers = vec![(USERS[1].into(), 300), (USERS[2].into(), 200)];
+    let res = ft.send(USERS[0], FTAction::BatchTransfer(transfers.clone()));
+    assert!(res.contains(&(
+        USERS[0],
+        FTEvent::BatchTransfer {
+            from: USERS[0].into(),
+            transfers,
+            total: 500,
+        }
+        .encode()
+    )));
+
+    let res = ft.send(USERS[0], FTAction::BalanceOf(USERS[0].into()));
+    assert!(res.contains(&(USERS[0], FTEvent::Balance(999500).encode())));
+    let res = ft.send(USERS[0], FTAction::BalanceOf(USERS[1].into()));
+    assert!(res.contains(&(USERS[0], FTEvent::Balance(300).encode())));
+    let res = ft.send(USERS[0], FTAction::BalanceOf(USERS[2].into()));
+    assert!(res.contains(&(USERS[0], FTEvent::Balance(200).encode())));
+}
+
+#[test]
+fn batch_transfer_gas() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let ft = Program::current(&sys);
+    // each batch pays new recipients, so every transfer inserts a balance
+    let batch = |recipients: core::ops::Range<u64>| {
+        let transfers: Vec<_> = recipients.map(|id| (id.into(), 10)).collect();
+        let res = ft.send(USERS[0], FTAction::BatchTransfer(transfers));
+        assert!(!res.main_failed());
+        u64::from(res.main_gas_burned())
+    };
+
+    let res = ft.send(
+        USERS[0],
+        FTAction::Transfer {
+            to: 99.into(),
+            amount: 10,
+            tx_id: None,
+        },
+    );
+    assert!(!res.main_failed());
+    let transfer_gas = u64::from(res.main_gas_burned());
+    let one_recipient_gas = batch(100..101);
+    let many_recipients_gas = batch(200..300);
+    let gas_per_recipient = (many_recipients_gas - one_recipient_gas) / 99;
+    println!("gas per batch recipient: {gas_per_recipient}, gas per transfer: {transfer_gas}");
+
+    // a batch recipient costs less than a separate transfer message
+    assert!(gas_per_recipient < transfer_gas);
+    assert!(many_recipients_gas < 100 * transfer_gas);
+
+    let res = ft.send(USERS[0], FTAction::BalanceOf(USERS[0].into()));
+    assert!(res.contains(&(USERS[0], FTEvent::Balance(998980).encode())));
+}
+
+#[test]
+fn batch_transfer_failures() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let ft = sys.get_program(1);
+
+    // must fail since the total exceeds the balance even though each amount doesn't
+    let res = ft.send(
+        USERS[0],
+        FTAction::BatchTransfer(vec![(USERS[1].into(), 600000), (USERS[2].into(), 600000)]),
+    );
+    assert!(res.contains(&(
+        USERS[0],
+        FTEvent::Err(FTError::InsufficientBalance).encode()
+    )));
+
+    // must fail since the batch contains the zero address
+    let res = ft.send(
+        USERS[0],
+        FTAction::BatchTransfer(vec![(USERS[1].into(), 100), (0.into(), 100)]),
+    );
+    assert!(res.contains(&(USERS[0], FTEvent::Err(FTError::ZeroAddress).encode())));
+
+    // must fail since the batch is empty
+    let res = ft.send(USERS[0], FTAction::BatchTransfer(vec![]));
+    assert!(res.contains(&(USERS[0], FTEvent::Err(FTError::EmptyBatch).encode())));
+
+    // check that no tokens moved
+    let res = ft.send(USERS[0], FTAction::BalanceOf(USERS[1].into()));
+    assert!(res.contains(&(USERS[0], FTEvent::Balance(0).encode())));
+}
+
+#[test]
+fn batch_mint() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let ft = sys.get_program(1);
+
+    let mints = vec![(USERS[1].into(), 300), (USERS[2].into(), 200)];
+
+    // must fail since `USERS[1]` is not a minter
+    let res = ft.send(USERS[1], FTAction::BatchMint(mints.clone()));
+    assert!(res.contains(&(USERS[1], FTEvent::Err(FTError::NotMinter).encode())));
+
+    let res = ft.send(USERS[0], FTAction::BatchMint(mints.clone()));
+    assert!(res.contains(&(
+        USERS[0],
+        FTEvent::BatchTransfer {
+            from: 0.into(),
+            transfers: mints,
+            total: 500,
+        }
+        .encode()
+    )));
+
+    let res = ft.send(USERS[0], FTAction::TotalSupply);
+    assert!(res.contains(&(USERS[0], FTEvent::TotalSupply(1000500).encode())));
+    let res = ft.send(USERS[0], FTAction::BalanceOf(USERS[1].into()));
+    assert!(res.contains(&(USERS[0], FTEvent::Balance(300).encode())));
+}
+
+#[test]
+fn snapshot() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let ft = sys.get_program(1);
+
+    let res = ft.send(USERS[0], FTAction::Snapshot);
+    assert!(res.contains(&(USERS[0], FTEvent::Snapshot(1).encode())));
+
+    // must fail since only admin or snapshotter can take snapshots
+    let res = ft.send(USERS[1], FTAction::Snapshot);
+    assert!(res.contains(&(USERS[1], FTEvent::Err(FTError::NotSnapshotter).encode())));
+
+    // must fail since only admin can set the snapshotter
+    let res = ft.send(USERS[1], FTAction::SetSnapshotter(Some(USERS[1].into())));
+    assert!(res.contains(&(USERS[1], FTEvent::Err(FTError::NotAdmin).encode())));
+
+    let res = ft.send(USERS[0], FTAction::SetSnapshotter(Some(USERS[1].into())));
+    assert!(res.contains(&(
+        USERS[0],
+        FTEvent::SnapshotterSet(Some(USERS[1].into())).encode()
+    )));
+
+    // balances keep changing between snapshots
+    let res = ft.send(
+        USERS[0],
+        FTAction::Transfer {
+            to: USERS[1].into(),
+            amount: 500,
+            tx_id: None,
+        },
+    );
+    assert!(!res.main_failed());
+
+    let res = ft.send(USERS[1], FTAction::Snapshot);
+    assert!(res.contains(&(USERS[1], FTEvent::Snapshot(2).encode())));
+}
+
+#[test]
+fn balances_at_snapshots() {
+    let (alice, bob, carol) = (ActorId::from(1), ActorId::from(2), ActorId::from(3));
+    let mut ft = FungibleToken::default();
+    let mint = |ft: &mut FungibleToken, to: &ActorId, amount: u128| {
+        ft.checkpoint_total_supply();
+        ft.total_supply += amount;
+        ft.credit(to, amount);
+    };
+    let burn = |ft: &mut FungibleToken, from: &ActorId, amount: u128| {
+        ft.debit(from, amount).unwrap();
+        ft.checkpoint_total_supply();
+        ft.total_supply -= amount;
+    };
+
+    // nothing is recorded before the first snapshot
+    mint(&mut ft, &alice, 1000);
+    assert!(ft.balance_checkpoints.is_empty());
+    assert!(ft.total_supply_checkpoints.is_empty());
+
+    ft.snapshot_id = 1;
+    ft.transfer_tokens(&alice, &bob, 300).unwrap();
+    // only the first change after a snapshot is recorded
+    ft.transfer_tokens(&alice, &bob, 100).unwrap();
+    assert_eq!(ft.balance_checkpoints[&alice], vec![(1, 1000)]);
+
+    // snapshot 2 is taken without any changes before snapshot 3
+    ft.snapshot_id = 3;
+    mint(&mut ft, &bob, 500);
+    burn(&mut ft, &alice, 200);
+    ft.snapshot_id = 4;
+
+    let balances_at = |account: &ActorId| {
+        (1..=4)
+            .map(|snapshot_id| ft.balance_of_at(account, snapshot_id))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(balances_at(&alice), vec![1000, 600, 600, 400]);
+    assert_eq!(balances_at(&bob), vec![0, 400, 400, 900]);
+    // the account without checkpoints has its current balance at every snapshot
+    assert_eq!(balances_at(&carol), vec![0, 0, 0, 0]);
+    let total_supply_at = (1..=4)
+        .map(|snapshot_id| ft.total_supply_at(snapshot_id))
+        .collect::<Vec<_>>();
+    assert_eq!(total_supply_at, vec![1000, 1000, 1000, 1300]);
+}
+
+#[test]
+fn value_at_checkpoints() {
+    let mut checkpoints = Vec::new();
+    checkpoint(&mut checkpoints, 2, 10);
+    checkpoint(&mut checkpoints, 2, 15);
+    checkpoint(&mut checkpoints, 5, 20);
+    checkpoint(&mut checkpoints, 9, 30);
+    assert_eq!(checkpoints, vec![(2, 10), (5, 20), (9, 30)]);
+
+    let values = (1..=10)
+        .map(|snapshot_id| value_at(&checkpoints, snapshot_id, 40))
+        .collect::<Vec<_>>();
+    assert_eq!(values, vec![10, 10, 20, 20, 20, 30, 30, 30, 30, 40]);
+    assert_eq!(value_at(&[], 1, 40), 40);
+}
+
+#[test]
+#[should_panic]
+fn balance_at_missing_snapshot() {
+    let ft = FungibleToken {
+        snapshot_id: 1,
+        ..Default::default()
+    };
+    ft.balance_of_at(&ActorId::from(1), 2);
+}
+
+fn sign_permit(keypair: &Keypair, payload: PermitPayload) -> [u8; 64] {
+    keypair
+        .sign_simple(b"substrate", &payload.encode())
+        .to_bytes()
+}
+
+#[test]
+fn permit() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let ft = sys.get_program(1);
+    let keypair = MiniSecretKey::from_bytes(&[7; 32])
+        .unwrap()
+        .expand_to_keypair(ExpansionMode::Ed25519);
+    let owner = ActorId::new(keypair.public.to_bytes());
+    let payload = |amount, deadline, nonce| PermitPayload {
+        token: 1.into(),
+        owner,
+        spender: USERS[2].into(),
+        amount,
+        deadline,
+        nonce,
+    };
+    let permit = |amount, deadline, nonce, signature| FTAction::Permit {
+        owner,
+        spender: USERS[2].into(),
+        amount,
+        deadline,
+        nonce,
+        signature,
+    };
+
+    let res = ft.send(
+        USERS[0],
+        FTAction::Mint {
+            to: owner,
+            amount: 1000,
+        },
+    );
+    assert!(!res.main_failed());
+
+    // anyone can relay the signed permit
+    let signature = sign_permit(&keypair, payload(500, u64::MAX, 0));
+    let res = ft.send(USERS[1], permit(500, u64::MAX, 0, signature));
+    assert!(res.contains(&(
+        USERS[1],
+        FTEvent::Approve {
+            from: owner,
+            to: USERS[2].into(),
+            amount: 500,
+        }
+        .encode()
+    )));
+    let res = ft.send(
+        USERS[2],
+        FTAction::TransferFrom {
+            from: owner,
+            to: USERS[2].into(),
+            amount: 500,
+            tx_id: None,
+        },
+    );
+    assert!(res.contains(&(
+        USERS[2],
+        FTEvent::TransferFrom {
+            from: owner,
+            to: USERS[2].into(),
+            amount: 500,
+            allowance: 0,
+        }
+        .encode()
+    )));
+
+    // must fail since the nonce has been used
+    let res = ft.send(USERS[1], permit(500, u64::MAX, 0, signature));
+    assert!(res.contains(&(USERS[1], FTEvent::Err(FTError::InvalidNonce).encode())));
+
+    // must fail since the owner signed another amount
+    let signature = sign_permit(&keypair, payload(100, u64::MAX, 1));
+    let res = ft.send(USERS[1], permit(700, u64::MAX, 1, signature));
+    assert!(res.contains(&(USERS[1], FTEvent::Err(FTError::InvalidSignature).encode())));
+
+    // must fail since the deadline has passed
+    sys.spend_blocks(5000);
+    let signature = sign_permit(&keypair, payload(100, 1, 1));
+    let res = ft.send(USERS[1], permit(100, 1, 1, signature));
+    assert!(res.contains(&(USERS[1], FTEvent::Err(FTError::PermitExpired).encode())));
+}
+
+#[test]
+fn vesting() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let ft = sys.get_program(1);
+    let create_vesting = |beneficiary: u64, start, cliff, duration| FTAction::CreateVesting {
+        beneficiary: beneficiary.into(),
+        amount: 500,
+        start,
+        cliff,
+        duration,
+    };
+
+    // must fail since only admin can create vestings
+    let res = ft.send(USERS[1], create_vesting(USERS[1], 0, 0, 0));
+    assert!(res.contains(&(USERS[1], FTEvent::Err(FTError::NotAdmin).encode())));
+    // must fail since the cliff is longer than the vesting
+    let res = ft.send(USERS[0], create_vesting(USERS[1], 0, 1000, 100));
+    assert!(res.contains(&(USERS[0], FTEvent::Err(FTError::InvalidVesting).encode())));
+
+    // the vesting is over, so all the tokens are released at once
+    let res = ft.send(USERS[0], create_vesting(USERS[1], 0, 0, 0));
+    assert!(res.contains(&(
+        USERS[0],
+        FTEvent::VestingCreated {
+            beneficiary: USERS[1].into(),
+            amount: 500,
+        }
+        .encode()
+    )));
+    let res = ft.send(USERS[0], FTAction::BalanceOf(1.into()));
+    assert!(res.contains(&(USERS[0], FTEvent::Balance(500).encode())));
+    let res = ft.send(USERS[1], FTAction::Release);
+    assert!(res.contains(&(
+        USERS[1],
+        FTEvent::VestingReleased {
+            beneficiary: USERS[1].into(),
+            amount: 500,
+        }
+        .encode()
+    )));
+    let res = ft.send(USERS[1], FTAction::BalanceOf(USERS[1].into()));
+    assert!(res.contains(&(USERS[1], FTEvent::Balance(500).encode())));
+    let res = ft.send(USERS[1], FTAction::Release);
+    assert!(res.contains(&(USERS[1], FTEvent::Err(FTError::NoVesting).encode())));
+
+    // the vesting hasn't started, so nothing can be released
+    let res = ft.send(USERS[0], create_vesting(USERS[2], u64::MAX, 0, 1000));
+    assert!(!res.main_failed());
+    let res = ft.send(USERS[0], create_vesting(USERS[2], 0, 0, 0));
+    assert!(res.contains(&(USERS[0], FTEvent::Err(FTError::VestingExists).encode())));
+    let res = ft.send(USERS[2], FTAction::Release);
+    assert!(res.contains(&(USERS[2], FTEvent::Err(FTError::NothingToRelease).encode())));
+
+    // must fail since only admin can revoke vestings
+    let res = ft.send(USERS[2], FTAction::RevokeVesting(USERS[2].into()));
+    assert!(res.contains(&(USERS[2], FTEvent::Err(FTError::NotAdmin).encode())));
+    let res = ft.send(USERS[0], FTAction::RevokeVesting(USERS[2].into()));
+    assert!(res.contains(&(
+        USERS[0],
+        FTEvent::VestingRevoked {
+            beneficiary: USERS[2].into(),
+            released: 0,
+            returned: 500,
+        }
+        .encode()
+    )));
+    let res = ft.send(USERS[0], FTAction::BalanceOf(USERS[0].into()));
+    assert!(res.contains(&(USERS[0], FTEvent::Balance(999500).encode())));
+}
+
+#[test]
+fn vesting_schedule() {
+    let vesting = Vesting {
+        amount: 1000,
+        released: 0,
+        start: 100,
+        cliff: 100,
+        duration: 1000,
+    };
+
+    // nothing vests before the start and until the cliff is over
+    assert_eq!(vested_amount(&vesting, 0), 0);
+    assert_eq!(vested_amount(&vesting, 199), 0);
+    // the tokens vested during the cliff are released at once when it's over
+    assert_eq!(vested_amount(&vesting, 200), 100);
+    // then the tokens vest linearly
+    assert_eq!(vested_amount(&vesting, 201), 101);
+    assert_eq!(vested_amount(&vesting, 600), 500);
+    assert_eq!(vested_amount(&vesting, 1099), 999);
+    assert_eq!(vested_amount(&vesting, 1100), 1000);
+    assert_eq!(vested_amount(&vesting, u64::MAX), 1000);
+
+    // a vesting without a cliff and a duration vests at once, but not before the start
+    let instant = Vesting {
+        start: 500,
+        cliff: 0,
+        duration: 0,
+        ..vesting
+    };
+    assert_eq!(vested_amount(&instant, 499), 0);
+    assert_eq!(vested_amount(&instant, 500), 1000);
+
+    // the amount doesn't overflow for the largest vestings
+    let vesting = Vesting {
+        amount: u128::MAX,
+        start: 0,
+        cliff: 0,
+        duration: u64::MAX,
+        ..vesting
+    };
+    assert_eq!(
+        vested_amount(&vesting, u64::MAX - 1),
+        u128::MAX - u64::MAX as u128 - 2
+    );
+}
+
+#[test]
+fn vested_amounts() {
+    let beneficiary = ActorId::from(1);
+    let mut ft = FungibleToken::default();
+    assert_eq!(ft.vested_amounts(&beneficiary, 500), (0, 0));
+
+    ft.vestings.insert(
+        beneficiary,
+        Vesting {
+            amount: 1000,
+            released: 0,
+            start: 100,
+            cliff: 100,
+            duration: 1000,
+        },
+    );
+    assert_eq!(ft.vested_amounts(&beneficiary, 150), (0, 1000));
+    assert_eq!(ft.vested_amounts(&beneficiary, 600), (500, 500));
+    assert_eq!(ft.vested_amounts(&beneficiary, 2000), (1000, 0));
+}
+
+#[test]
+fn release_in_parts() {
+    let mut vesting = Vesting {
+        amount: 1000,
+        released: 0,
+        start: 100,
+        cliff: 100,
+        duration: 1000,
+    };
+    assert_eq!(releasable_amount(&vesting, 199), 0);
+
+    // the first claim releases the tokens vested so far
+    assert_eq!(releasable_amount(&vesting, 600), 500);
+    vesting.released += 500;
+    assert_eq!(releasable_amount(&vesting, 600), 0);
+
+    // the second claim releases only the tokens vested since the first one
+    assert_eq!(releasable_amount(&vesting, 850), 250);
+    vesting.released += 250;
+    assert_eq!(releasable_amount(&vesting, 2000), 250);
 }
@@ -67,24 +67,52 @@ impl Lottery {
             .any(|player| player.player_id == msg::source())
     }
 
+    /// Transfers `amount` tokens from the lottery account to `recipient` account.
+    /// Arguments:
+    /// * `to`: recipient account
+    /// * `amount`: amount of tokens
+    async fn transfer_tokens(&mut self, to: &ActorId, amount_tokens: u128) -> Result<(), FTError> {
+        self.send_transfer(FTAction::Transfer {
+            to: *to,
+            amount: amount_tokens,
+            tx_id: None,
+        })
+        .await
+    }
+
     /// Transfers `amount` tokens from `sender` account to `recipient` account.
+    /// `sender` must approve the lottery to spend `amount` of its tokens.
     /// Arguments:
     /// * `from`: sender account
     /// * `to`: recipient account
     /// * `amount`: amount of tokens
-    async fn transfer_tokens(&mut self, from: &ActorId, to: &ActorId, amount_tokens: u128) {
-        let _transfer_response: FTEvent = msg::send_and_wait_for_reply(
-            self.token_address.unwrap(),
-            FTAction::Transfer {
-                from: *from,
-                to: *to,
-                amount: amount_tokens,
-            },
-            0,
-        )
-        .expect("Error in sending message")
+    async fn transfer_from_tokens(
+        &mut self,
+        from: &ActorId,
+        to: &ActorId,
+        amount_tokens: u128,
+    ) -> Result<(), FTError> {
+        self.send_transfer(FTAction::TransferFrom {
+            from: *from,
+            to: *to,
+            amount: amount_tokens,
+            tx_id: None,
+        })
         .await
-        .expect("Error in transfer");
+    }
+
+    // sends a transfer to the Fungible Token contract and returns its error if it failed
+    async fn send_transfer(&mut self, action: FTAction) -> Result<(), FTError> {
+        let transfer_response: FTEvent =
+            msg::send_and_wait_for_reply(self.token_address.unwrap(), action, 0)
+                .expect("Error in sending message")
+                .await
+                .expect("Error in decoding reply");
+        match transfer_response {
+            FTEvent::Transfer { .. } | FTEvent::TransferFrom { .. } => Ok(()),
+            FTEvent::Err(error) => Err(error),
+            _ => panic!("Unexpected reply to a transfer"),
+        }
     }
 
     /// Called by a player in order to participate in lottery
@@ -102,8 +130,19 @@ impl Lottery {
             };
 
             if self.token_address.is_some() {
-                self.transfer_tokens(&msg::source(), &exec::program_id(), amount)
-                    .await;
+                match self
+                    .transfer_from_tokens(&msg::source(), &exec::program_id(), amount)
+                    .await
+                {
+                    Ok(()) => {}
+                    Err(FTError::InsufficientBalance) => {
+                        panic!("enter(): Player doesn't have enough tokens")
+                    }
+                    Err(FTError::InsufficientAllowance) => {
+                        panic!("enter(): Player must approve the lottery to spend the tokens")
+                    }
+                    Err(error) => panic!("enter(): Error in transfer: {error:?}"),
+                }
 
                 self.lottery_balance += amount;
                 debug!("Add in Fungible Token: {}", amount);
@@ -142,8 +181,12 @@ impl Lottery {
 
             if self.token_address.is_some() {
                 debug!("Transfer tokens to the winner");
-                self.transfer_tokens(&exec::program_id(), &player.player_id, self.lottery_balance)
-                    .await;
+                if let Err(error) = self
+                    .transfer_tokens(&player.player_id, self.lottery_balance)
+                    .await
+                {
+                    panic!("pick_winner(): Error in transfer: {error:?}");
+                }
 
                 self.lottery_balance = 0;
             } else {
@@ -5,7 +5,7 @@ use std::println;
 
 use codec::Encode;
 use ft_io::*;
-use gstd::String;
+use gstd::{vec, String};
 use gtest::{Program, System};
 use lt_io::*;
 const USERS: &'static [u64] = &[1, 2, 3, 4, 5];
@@ -29,22 +29,57 @@ fn init_fungible_token(sys: &System) {
         InitConfig {
             name: String::from("MyToken"),
             symbol: String::from("MTK"),
+            decimals: 18,
+            admin: USERS[2].into(),
+            minters: vec![USERS[2].into()],
+            max_supply: None,
         },
     );
 
     assert!(res.log().is_empty());
 
-    let res = ft.send(USERS[3], FTAction::Mint(1000));
+    let res = ft.send(
+        USERS[2],
+        FTAction::Mint {
+            to: USERS[3].into(),
+            amount: 1000,
+        },
+    );
     assert!(!res.main_failed());
 
     let res = ft.send(USERS[2], FTAction::BalanceOf(USERS[3].into()));
     assert!(res.contains(&(USERS[2], FTEvent::Balance(1000).encode())));
 
-    let res = ft.send(USERS[4], FTAction::Mint(2000));
+    let res = ft.send(
+        USERS[2],
+        FTAction::Mint {
+            to: USERS[4].into(),
+            amount: 2000,
+        },
+    );
     assert!(!res.main_failed());
 
     let res = ft.send(USERS[2], FTAction::BalanceOf(USERS[4].into()));
     assert!(res.contains(&(USERS[2], FTEvent::Balance(2000).encode())));
+
+    // approve the lottery to take the contributions
+    let res = ft.send(
+        USERS[3],
+        FTAction::Approve {
+            to: USERS[1].into(),
+            amount: 1000,
+        },
+    );
+    assert!(!res.main_failed());
+
+    let res = ft.send(
+        USERS[4],
+        FTAction::Approve {
+            to: USERS[1].into(),
+            amount: 2000,
+        },
+    );
+    assert!(!res.main_failed());
 }
 
 #[test]
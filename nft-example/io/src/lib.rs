@@ -2,6 +2,10 @@
 
 use codec::{Decode, Encode};
 use gstd::{prelude::*, ActorId};
+pub use non_fungible_token::attributes::AttributeValue;
+pub use non_fungible_token::history::{HistoryKind, HistoryRecord};
+pub use non_fungible_token::royalties::Payout;
+pub use non_fungible_token::token::TokenMetadata;
 use primitive_types::U256;
 use scale_info::TypeInfo;
 
@@ -9,13 +13,82 @@ use scale_info::TypeInfo;
 pub enum Action {
     Mint,
     Burn(U256),
-    Transfer { to: ActorId, token_id: U256 },
-    Approve { to: ActorId, token_id: U256 },
-    ApproveForAll { to: ActorId, approved: bool },
+    Transfer {
+        to: ActorId,
+        token_id: U256,
+    },
+    Approve {
+        to: ActorId,
+        token_id: U256,
+    },
+    ApproveForAll {
+        to: ActorId,
+        approved: bool,
+    },
     OwnerOf(U256),
     BalanceOf(ActorId),
+    IsApprovedForAll {
+        owner: ActorId,
+        operator: ActorId,
+    },
+    TokensForOwner(ActorId),
+    TotalSupply,
+    TokenByIndex(U256),
+    SafeTransfer {
+        to: ActorId,
+        token_id: U256,
+        data: Vec<u8>,
+    },
+    TokenURI(U256),
+    /// Replaces the token metadata, if `freeze` is true it can't be changed anymore.
+    /// Only the contract owner can set metadata.
+    SetTokenMetadata {
+        token_id: U256,
+        metadata: TokenMetadata,
+        freeze: bool,
+    },
+    /// Burns the token of any owner, even a non-transferable one.
+    /// Only the contract owner can revoke tokens.
+    Revoke(U256),
+    /// Overrides the collection `transferable` flag for the token, `None` removes the override.
+    /// Only the contract owner can set it.
+    SetTransferable {
+        token_id: U256,
+        transferable: Option<bool>,
+    },
+    /// Only the attribute updaters can set attributes.
+    SetAttribute {
+        token_id: U256,
+        key: String,
+        value: AttributeValue,
+    },
+    /// Only the attribute updaters can remove attributes.
+    RemoveAttribute {
+        token_id: U256,
+        key: String,
+    },
+    /// Allows or disallows the account to change the token attributes.
+    /// Only the contract owner can manage the attribute updaters.
+    SetAttributeUpdater {
+        account: ActorId,
+        allowed: bool,
+    },
+    TokenAttributes(U256),
+    /// Replies with at most `limit` history records of the token from the oldest one, skipping the first `offset`.
+    TokenHistory {
+        token_id: U256,
+        offset: u32,
+        limit: u32,
+    },
+    /// Replies with at most `limit` history records involving the account from the oldest one, skipping the first `offset`.
+    AccountActivity {
+        account: ActorId,
+        offset: u32,
+        limit: u32,
+    },
 }
 
+/// The first variants mirror `non_fungible_token::Event`, which the library replies with.
 #[derive(Debug, Encode, Decode, TypeInfo)]
 pub enum Event {
     Transfer {
@@ -35,6 +108,43 @@ pub enum Event {
     },
     OwnerOf(ActorId),
     BalanceOf(U256),
+    IsApprovedForAll(bool),
+    TokensForOwner(Vec<U256>),
+    TotalSupply(U256),
+    TokenByIndex(U256),
+    TransferRejected {
+        from: ActorId,
+        to: ActorId,
+        token_id: U256,
+    },
+    TokenURI(String),
+    TokenMetadataSet {
+        token_id: U256,
+        frozen: bool,
+    },
+    RoyaltyInfo(Payout),
+    RoyaltiesSet {
+        token_id: U256,
+    },
+    AttributeSet {
+        token_id: U256,
+        key: String,
+        value: AttributeValue,
+    },
+    AttributeRemoved {
+        token_id: U256,
+        key: String,
+    },
+    TransferableSet {
+        token_id: U256,
+        transferable: bool,
+    },
+    AttributeUpdaterSet {
+        account: ActorId,
+        allowed: bool,
+    },
+    TokenAttributes(BTreeMap<String, AttributeValue>),
+    History(Vec<HistoryRecord>),
 }
 
 #[derive(Debug, Encode, Decode, TypeInfo)]
@@ -42,4 +152,9 @@ pub struct InitConfig {
     pub name: String,
     pub symbol: String,
     pub base_uri: String,
+    /// The number of the latest history records kept per token and per account,
+    /// zero disables the history.
+    pub history_retention: u32,
+    /// False makes the tokens soulbound: they can't be transferred or approved
+    pub transferable: bool,
 }
@@ -2,7 +2,7 @@
 #![feature(const_btree_new)]
 
 use codec::Encode;
-use gstd::{debug, exec, msg, prelude::*, ActorId};
+use gstd::{debug, msg, prelude::*, ActorId};
 use primitive_types::U256;
 
 pub mod state;
@@ -10,7 +10,10 @@ pub use state::{State, StateReply};
 
 pub use nft_example_io::{Action, Event, InitConfig};
 
+use non_fungible_token::attributes::NonFungibleTokenAttributes;
 use non_fungible_token::base::NonFungibleTokenBase;
+use non_fungible_token::enumerable::NonFungibleTokenEnumerable;
+use non_fungible_token::metadata::NonFungibleTokenMetadata;
 use non_fungible_token::NonFungibleToken;
 
 const ZERO_ID: ActorId = ActorId::new([0u8; 32]);
@@ -30,58 +33,13 @@ static mut CONTRACT: NFT = NFT {
 
 impl NFT {
     fn mint(&mut self) {
-        self.token.owner_by_id.insert(self.token_id, msg::source());
-        let balance = *self
-            .token
-            .balances
-            .get(&msg::source())
-            .unwrap_or(&U256::zero());
-        self.token
-            .balances
-            .insert(msg::source(), balance.saturating_add(U256::one()));
-
-        msg::reply(
-            Event::Transfer {
-                from: ZERO_ID,
-                to: msg::source(),
-                token_id: self.token_id,
-            },
-            0,
-        )
-        .unwrap();
+        self.token.mint_to(&msg::source(), self.token_id, None);
         self.token_id = self.token_id.saturating_add(U256::one());
     }
 
-    fn burn(&mut self, token_id: U256) {
-        if !self.token.exists(token_id) {
-            panic!("NonFungibleToken: Token does not exist");
-        }
-        self.check_owner(token_id);
-        self.token.token_approvals.remove(&token_id);
-        self.token.owner_by_id.remove(&token_id);
-        let balance = *self
-            .token
-            .balances
-            .get(&msg::source())
-            .unwrap_or(&U256::zero());
-        self.token
-            .balances
-            .insert(msg::source(), balance.saturating_sub(U256::one()));
-        msg::reply(
-            Event::Transfer {
-                from: msg::source(),
-                to: ZERO_ID,
-                token_id,
-            },
-            0,
-        )
-        .unwrap();
-    }
-    fn check_owner(&self, token_id: U256) {
-        if self.token.owner_by_id.get(&token_id).unwrap_or(&ZERO_ID) != &msg::source()
-            || self.token.owner_by_id.get(&token_id).unwrap_or(&ZERO_ID) != &exec::origin()
-        {
-            panic!("Only owner can transfer");
+    fn check_owner(&self) {
+        if msg::source() != self.owner {
+            panic!("Only owner can make that action");
         }
     }
 }
@@ -98,19 +56,94 @@ gstd::metadata! {
             output: StateReply,
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn handle() {
+#[gstd::async_main]
+async unsafe fn main() {
     let action: Action = msg::load().expect("Could not load Action");
     match action {
         Action::Mint => {
             CONTRACT.mint();
         }
-        Action::Burn(amount) => {
-            CONTRACT.burn(amount);
+        Action::Burn(token_id) => {
+            CONTRACT.token.burn(token_id);
         }
         Action::Transfer { to, token_id } => {
             CONTRACT.token.transfer(&to, token_id);
         }
+        Action::SafeTransfer { to, token_id, data } => {
+            CONTRACT.token.safe_transfer(&to, token_id, data).await;
+        }
+        Action::TokenURI(token_id) => {
+            CONTRACT.token.token_uri(token_id);
+        }
+        Action::SetTokenMetadata {
+            token_id,
+            metadata,
+            freeze,
+        } => {
+            CONTRACT.check_owner();
+            CONTRACT
+                .token
+                .set_token_metadata(token_id, metadata, freeze);
+        }
+        Action::Revoke(token_id) => {
+            CONTRACT.check_owner();
+            CONTRACT.token.revoke(token_id);
+        }
+        Action::SetTransferable {
+            token_id,
+            transferable,
+        } => {
+            CONTRACT.check_owner();
+            CONTRACT
+                .token
+                .set_token_transferable(token_id, transferable);
+            msg::reply(
+                Event::TransferableSet {
+                    token_id,
+                    transferable: CONTRACT.token.is_transferable(token_id),
+                },
+                0,
+            )
+            .unwrap();
+        }
+        Action::SetAttribute {
+            token_id,
+            key,
+            value,
+        } => {
+            CONTRACT.token.set_attribute(token_id, key, value);
+        }
+        Action::RemoveAttribute { token_id, key } => {
+            CONTRACT.token.remove_attribute(token_id, key);
+        }
+        Action::SetAttributeUpdater { account, allowed } => {
+            CONTRACT.check_owner();
+            CONTRACT.token.set_attribute_updater(&account, allowed);
+            msg::reply(Event::AttributeUpdaterSet { account, allowed }, 0).unwrap();
+        }
+        Action::TokenAttributes(token_id) => {
+            msg::reply(
+                Event::TokenAttributes(CONTRACT.token.attributes_of(token_id)),
+                0,
+            )
+            .unwrap();
+        }
+        Action::TokenHistory {
+            token_id,
+            offset,
+            limit,
+        } => {
+            let history = CONTRACT.token.token_history(token_id, offset, limit);
+            msg::reply(Event::History(history), 0).unwrap();
+        }
+        Action::AccountActivity {
+            account,
+            offset,
+            limit,
+        } => {
+            let history = CONTRACT.token.account_activity(&account, offset, limit);
+            msg::reply(Event::History(history), 0).unwrap();
+        }
         Action::Approve { to, token_id } => {
             CONTRACT.token.approve(&to, token_id);
         }
@@ -125,6 +158,18 @@ pub unsafe extern "C" fn handle() {
         Action::BalanceOf(input) => {
             CONTRACT.token.balance_of(&input);
         }
+        Action::IsApprovedForAll { owner, operator } => {
+            CONTRACT.token.is_approved_for_all(&owner, &operator);
+        }
+        Action::TokensForOwner(owner) => {
+            CONTRACT.token.tokens_for_owner(&owner);
+        }
+        Action::TotalSupply => {
+            CONTRACT.token.total_supply();
+        }
+        Action::TokenByIndex(index) => {
+            CONTRACT.token.token_by_index(index);
+        }
     }
 }
 
@@ -135,6 +180,8 @@ pub unsafe extern "C" fn init() {
     CONTRACT
         .token
         .init(config.name, config.symbol, config.base_uri);
+    CONTRACT.token.history_retention = config.history_retention;
+    CONTRACT.token.transferable = config.transferable;
     CONTRACT.owner = msg::source();
 }
 
@@ -165,6 +212,36 @@ pub unsafe extern "C" fn meta_state() -> *mut [i32; 2] {
                 .unwrap_or(&ZERO_ID);
             StateReply::GetApproved(*approved_address)
         }
+        State::IsApprovedForAll { owner, operator } => {
+            StateReply::IsApprovedForAll(CONTRACT.token.is_operator(&owner, &operator))
+        }
+        State::TokenMetadata(token_id) => {
+            StateReply::TokenMetadata(CONTRACT.token.token_metadata_by_id.get(&token_id).cloned())
+        }
+        State::IsTransferable(token_id) => {
+            StateReply::IsTransferable(CONTRACT.token.is_transferable(token_id))
+        }
+        State::TokenAttributes(token_id) => {
+            StateReply::TokenAttributes(CONTRACT.token.attributes_of(token_id))
+        }
+        State::TokensWithAttribute { key, value } => {
+            StateReply::Tokens(CONTRACT.token.tokens_with_attribute(&key, value.as_ref()))
+        }
+        State::TokenHistory {
+            token_id,
+            offset,
+            limit,
+        } => StateReply::History(CONTRACT.token.token_history(token_id, offset, limit)),
+        State::AccountActivity {
+            account,
+            offset,
+            limit,
+        } => StateReply::History(CONTRACT.token.account_activity(&account, offset, limit)),
+        State::ContractMetadata => StateReply::ContractMetadata {
+            name: CONTRACT.token.name.clone(),
+            symbol: CONTRACT.token.symbol.clone(),
+            base_uri: CONTRACT.token.base_uri.clone(),
+        },
     }
     .encode();
 
@@ -1,5 +1,8 @@
 use codec::{Decode, Encode};
 use gstd::{prelude::*, ActorId};
+use non_fungible_token::attributes::AttributeValue;
+use non_fungible_token::history::HistoryRecord;
+use non_fungible_token::token::TokenMetadata;
 use primitive_types::U256;
 use scale_info::TypeInfo;
 
@@ -7,8 +10,36 @@ use scale_info::TypeInfo;
 pub enum State {
     BalanceOfUser(ActorId),
     TokenOwner(U256),
-    IsTokenOwner { account: ActorId, token_id: U256 },
+    IsTokenOwner {
+        account: ActorId,
+        token_id: U256,
+    },
     GetApproved(U256),
+    IsApprovedForAll {
+        owner: ActorId,
+        operator: ActorId,
+    },
+    TokenMetadata(U256),
+    ContractMetadata,
+    IsTransferable(U256),
+    /// Returns at most `limit` history records of the token from the oldest one, skipping the first `offset`
+    TokenHistory {
+        token_id: U256,
+        offset: u32,
+        limit: u32,
+    },
+    /// Returns at most `limit` history records involving the account from the oldest one, skipping the first `offset`
+    AccountActivity {
+        account: ActorId,
+        offset: u32,
+        limit: u32,
+    },
+    TokenAttributes(U256),
+    /// Returns the tokens having the attribute, with the given value if it is set
+    TokensWithAttribute {
+        key: String,
+        value: Option<AttributeValue>,
+    },
 }
 
 #[derive(Debug, Decode, Encode, TypeInfo)]
@@ -17,4 +48,15 @@ pub enum StateReply {
     TokenOwner(ActorId),
     IsTokenOwner(bool),
     GetApproved(ActorId),
+    IsApprovedForAll(bool),
+    TokenMetadata(Option<TokenMetadata>),
+    ContractMetadata {
+        name: String,
+        symbol: String,
+        base_uri: String,
+    },
+    History(Vec<HistoryRecord>),
+    IsTransferable(bool),
+    TokenAttributes(BTreeMap<String, AttributeValue>),
+    Tokens(Vec<U256>),
 }
@@ -0,0 +1,222 @@
+use gstd::ActorId;
+use gtest::{Program, System};
+use nft_example_io::*;
+const USERS: &'static [u64] = &[3, 4, 5];
+
+fn init_with_history(sys: &System, history_retention: u32) {
+    sys.init_logger();
+
+    let nft = Program::from_file(
+        &sys,
+        "../../apps/target/wasm32-unknown-unknown/release/nft_example.wasm",
+    );
+
+    let res = nft.send(
+        USERS[0],
+        InitConfig {
+            name: String::from("MyToken"),
+            symbol: String::from("MTK"),
+            base_uri: String::from(""),
+            history_retention,
+            transferable: true,
+        },
+    );
+    assert!(res.log().is_empty());
+}
+
+// returns the records without the block info, which depends on the test environment
+fn history(nft: &Program, action: Action) -> Vec<(HistoryKind, u64, ActorId, ActorId)> {
+    let res = nft.send(USERS[0], action);
+    match res.decoded_log::<Event>()[0].payload() {
+        Event::History(records) => records
+            .iter()
+            .map(|record| {
+                (
+                    record.kind,
+                    record.token_id.as_u64(),
+                    record.from,
+                    record.to,
+                )
+            })
+            .collect(),
+        event => panic!("Unexpected reply {event:?}"),
+    }
+}
+
+fn token_history(
+    nft: &Program,
+    token_id: u64,
+    offset: u32,
+    limit: u32,
+) -> Vec<(HistoryKind, u64, ActorId, ActorId)> {
+    history(
+        nft,
+        Action::TokenHistory {
+            token_id: token_id.into(),
+            offset,
+            limit,
+        },
+    )
+}
+
+fn account_activity(
+    nft: &Program,
+    account: u64,
+    offset: u32,
+    limit: u32,
+) -> Vec<(HistoryKind, u64, ActorId, ActorId)> {
+    history(
+        nft,
+        Action::AccountActivity {
+            account: account.into(),
+            offset,
+            limit,
+        },
+    )
+}
+
+#[test]
+fn history_is_disabled() {
+    let sys = System::new();
+    init_with_history(&sys, 0);
+    let nft = sys.get_program(1);
+    let res = nft.send(USERS[0], Action::Mint);
+    assert!(!res.main_failed());
+
+    assert!(token_history(&nft, 0, 0, 10).is_empty());
+    assert!(account_activity(&nft, USERS[0], 0, 10).is_empty());
+}
+
+#[test]
+fn transfers_approvals_and_burns() {
+    let sys = System::new();
+    init_with_history(&sys, 10);
+    let nft = sys.get_program(1);
+    let (zero, user0, user1) = (
+        ActorId::from(0),
+        ActorId::from(USERS[0]),
+        ActorId::from(USERS[1]),
+    );
+
+    let res = nft.send(USERS[0], Action::Mint);
+    assert!(!res.main_failed());
+    let res = nft.send(
+        USERS[0],
+        Action::Approve {
+            to: USERS[1].into(),
+            token_id: 0.into(),
+        },
+    );
+    assert!(!res.main_failed());
+    let res = nft.send(
+        USERS[1],
+        Action::Transfer {
+            to: USERS[1].into(),
+            token_id: 0.into(),
+        },
+    );
+    assert!(!res.main_failed());
+    let res = nft.send(USERS[1], Action::Burn(0.into()));
+    assert!(!res.main_failed());
+
+    assert_eq!(
+        token_history(&nft, 0, 0, 10),
+        vec![
+            (HistoryKind::Transfer, 0, zero, user0),
+            (HistoryKind::Approval, 0, user0, user1),
+            (HistoryKind::Transfer, 0, user0, user1),
+            (HistoryKind::Transfer, 0, user1, zero),
+        ]
+    );
+    assert_eq!(
+        account_activity(&nft, USERS[0], 0, 10),
+        vec![
+            (HistoryKind::Transfer, 0, zero, user0),
+            (HistoryKind::Approval, 0, user0, user1),
+            (HistoryKind::Transfer, 0, user0, user1),
+        ]
+    );
+    assert_eq!(
+        account_activity(&nft, USERS[1], 0, 10),
+        vec![
+            (HistoryKind::Approval, 0, user0, user1),
+            (HistoryKind::Transfer, 0, user0, user1),
+            (HistoryKind::Transfer, 0, user1, zero),
+        ]
+    );
+    // the zero address isn't an account, so its activity isn't kept
+    assert!(account_activity(&nft, 0, 0, 10).is_empty());
+}
+
+#[test]
+fn history_is_pruned_at_retention() {
+    let sys = System::new();
+    init_with_history(&sys, 3);
+    let nft = sys.get_program(1);
+    let (user0, user1, user2) = (
+        ActorId::from(USERS[0]),
+        ActorId::from(USERS[1]),
+        ActorId::from(USERS[2]),
+    );
+
+    let res = nft.send(USERS[0], Action::Mint);
+    assert!(!res.main_failed());
+    // the token goes around the users twice
+    for (from, to) in [(0, 1), (1, 2), (2, 0), (0, 1), (1, 2)] {
+        let res = nft.send(
+            USERS[from],
+            Action::Transfer {
+                to: USERS[to].into(),
+                token_id: 0.into(),
+            },
+        );
+        assert!(!res.main_failed());
+    }
+
+    // only the 3 latest records are kept
+    assert_eq!(
+        token_history(&nft, 0, 0, 10),
+        vec![
+            (HistoryKind::Transfer, 0, user2, user0),
+            (HistoryKind::Transfer, 0, user0, user1),
+            (HistoryKind::Transfer, 0, user1, user2),
+        ]
+    );
+    assert_eq!(
+        account_activity(&nft, USERS[0], 0, 10),
+        vec![
+            (HistoryKind::Transfer, 0, user0, user1),
+            (HistoryKind::Transfer, 0, user2, user0),
+            (HistoryKind::Transfer, 0, user0, user1),
+        ]
+    );
+}
+
+#[test]
+fn history_paging() {
+    let sys = System::new();
+    init_with_history(&sys, 10);
+    let nft = sys.get_program(1);
+    let (zero, user0) = (ActorId::from(0), ActorId::from(USERS[0]));
+
+    for _ in 0..5 {
+        let res = nft.send(USERS[0], Action::Mint);
+        assert!(!res.main_failed());
+    }
+    let mint = |token_id| (HistoryKind::Transfer, token_id, zero, user0);
+
+    assert_eq!(
+        account_activity(&nft, USERS[0], 0, 2),
+        vec![mint(0), mint(1)]
+    );
+    assert_eq!(
+        account_activity(&nft, USERS[0], 2, 2),
+        vec![mint(2), mint(3)]
+    );
+    assert_eq!(account_activity(&nft, USERS[0], 4, 2), vec![mint(4)]);
+    assert!(account_activity(&nft, USERS[0], 5, 2).is_empty());
+    assert!(account_activity(&nft, USERS[0], 0, 0).is_empty());
+    assert_eq!(token_history(&nft, 3, 0, 10), vec![mint(3)]);
+    // the token without records has an empty history
+    assert!(token_history(&nft, 5, 0, 10).is_empty());
+}
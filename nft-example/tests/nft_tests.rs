@@ -1,6 +1,7 @@
 use codec::Encode;
 use gtest::{Program, System};
 use nft_example_io::*;
+use std::collections::BTreeMap;
 const USERS: &'static [u64] = &[3, 4, 5];
 
 fn init_with_mint(sys: &System) {
@@ -17,6 +18,8 @@ fn init_with_mint(sys: &System) {
             name: String::from("MyToken"),
             symbol: String::from("MTK"),
             base_uri: String::from(""),
+            history_retention: 0,
+            transferable: true,
         },
     );
 
@@ -210,6 +213,42 @@ fn approve_and_transfer() {
     )));
 }
 
+#[test]
+fn safe_transfer_to_user() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let nft = sys.get_program(1);
+    let res = nft.send(
+        USERS[0],
+        Action::Approve {
+            to: USERS[1].into(),
+            token_id: 0.into(),
+        },
+    );
+    assert!(!res.main_failed());
+
+    // the token is transferred without the receiver call, since the caller is a user account
+    let res = nft.send(
+        USERS[1],
+        Action::SafeTransfer {
+            to: USERS[1].into(),
+            token_id: 0.into(),
+            data: vec![],
+        },
+    );
+    assert!(res.contains(&(
+        USERS[1],
+        Event::Transfer {
+            from: USERS[0].into(),
+            to: USERS[1].into(),
+            token_id: 0.into(),
+        }
+        .encode()
+    )));
+    let res = nft.send(USERS[0], Action::OwnerOf(0.into()));
+    assert!(res.contains(&(USERS[0], Event::OwnerOf(USERS[1].into()).encode())));
+}
+
 #[test]
 fn approve_for_all() {
     let sys = System::new();
@@ -282,3 +321,410 @@ fn approve_for_all() {
     );
     assert!(res.main_failed());
 }
+
+#[test]
+fn multiple_operators() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let nft = sys.get_program(1);
+
+    let res = nft.send(USERS[0], Action::Mint);
+    assert!(!res.main_failed());
+    for operator in [USERS[1], USERS[2]] {
+        let res = nft.send(
+            USERS[0],
+            Action::ApproveForAll {
+                to: operator.into(),
+                approved: true,
+            },
+        );
+        assert!(!res.main_failed());
+    }
+
+    // revoking one operator doesn't affect the other
+    let res = nft.send(
+        USERS[0],
+        Action::ApproveForAll {
+            to: USERS[1].into(),
+            approved: false,
+        },
+    );
+    assert!(!res.main_failed());
+    let res = nft.send(
+        USERS[0],
+        Action::IsApprovedForAll {
+            owner: USERS[0].into(),
+            operator: USERS[1].into(),
+        },
+    );
+    assert!(res.contains(&(USERS[0], Event::IsApprovedForAll(false).encode())));
+    let res = nft.send(
+        USERS[0],
+        Action::IsApprovedForAll {
+            owner: USERS[0].into(),
+            operator: USERS[2].into(),
+        },
+    );
+    assert!(res.contains(&(USERS[0], Event::IsApprovedForAll(true).encode())));
+
+    // must fail since `USERS[1]` is not an operator of `USERS[0]` anymore
+    let res = nft.send(
+        USERS[1],
+        Action::Transfer {
+            to: USERS[1].into(),
+            token_id: 0.into(),
+        },
+    );
+    assert!(res.main_failed());
+    let res = nft.send(
+        USERS[2],
+        Action::Transfer {
+            to: USERS[2].into(),
+            token_id: 0.into(),
+        },
+    );
+    assert!(!res.main_failed());
+    let res = nft.send(
+        USERS[2],
+        Action::Transfer {
+            to: USERS[1].into(),
+            token_id: 1.into(),
+        },
+    );
+    assert!(!res.main_failed());
+}
+
+#[test]
+fn operator_of_another_owner() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let nft = sys.get_program(1);
+
+    let res = nft.send(USERS[1], Action::Mint);
+    assert!(!res.main_failed());
+    let res = nft.send(
+        USERS[1],
+        Action::ApproveForAll {
+            to: USERS[2].into(),
+            approved: true,
+        },
+    );
+    assert!(!res.main_failed());
+
+    // must fail since `USERS[2]` is an operator of `USERS[1]`, not of `USERS[0]`
+    let res = nft.send(
+        USERS[2],
+        Action::Transfer {
+            to: USERS[2].into(),
+            token_id: 0.into(),
+        },
+    );
+    assert!(res.main_failed());
+    // the owner having an operator doesn't make anyone else an operator
+    let res = nft.send(
+        USERS[0],
+        Action::ApproveForAll {
+            to: USERS[2].into(),
+            approved: true,
+        },
+    );
+    assert!(!res.main_failed());
+    let res = nft.send(
+        USERS[1],
+        Action::Transfer {
+            to: USERS[1].into(),
+            token_id: 0.into(),
+        },
+    );
+    assert!(res.main_failed());
+}
+
+#[test]
+fn enumeration() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let nft = sys.get_program(1);
+    let res = nft.send(USERS[0], Action::Mint);
+    assert!(!res.main_failed());
+    let res = nft.send(USERS[0], Action::Mint);
+    assert!(!res.main_failed());
+    let res = nft.send(
+        USERS[0],
+        Action::Transfer {
+            to: USERS[1].into(),
+            token_id: 1.into(),
+        },
+    );
+    assert!(!res.main_failed());
+    let res = nft.send(USERS[0], Action::Burn(0.into()));
+    assert!(!res.main_failed());
+
+    let res = nft.send(USERS[0], Action::TokensForOwner(USERS[0].into()));
+    assert!(res.contains(&(USERS[0], Event::TokensForOwner(vec![2.into()]).encode())));
+    let res = nft.send(USERS[0], Action::TokensForOwner(USERS[1].into()));
+    assert!(res.contains(&(USERS[0], Event::TokensForOwner(vec![1.into()]).encode())));
+    let res = nft.send(USERS[0], Action::TotalSupply);
+    assert!(res.contains(&(USERS[0], Event::TotalSupply(2.into()).encode())));
+    let res = nft.send(USERS[0], Action::TokenByIndex(0.into()));
+    assert!(res.contains(&(USERS[0], Event::TokenByIndex(1.into()).encode())));
+    let res = nft.send(USERS[0], Action::TokenByIndex(1.into()));
+    assert!(res.contains(&(USERS[0], Event::TokenByIndex(2.into()).encode())));
+
+    // must fail since there are only two tokens
+    let res = nft.send(USERS[0], Action::TokenByIndex(2.into()));
+    assert!(res.main_failed());
+}
+
+#[test]
+fn token_metadata() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let nft = sys.get_program(1);
+    let set_metadata =
+        |media: Option<&str>, reference: Option<&str>, freeze| Action::SetTokenMetadata {
+            token_id: 0.into(),
+            metadata: TokenMetadata {
+                media: media.map(String::from),
+                reference: reference.map(String::from),
+                ..Default::default()
+            },
+            freeze,
+        };
+
+    // the URI is made of the base URI and the token ID until the metadata is set
+    let res = nft.send(USERS[0], Action::TokenURI(0.into()));
+    assert!(res.contains(&(USERS[0], Event::TokenURI(String::from("0")).encode())));
+
+    // must fail since only the contract owner can set metadata
+    let res = nft.send(USERS[1], set_metadata(None, Some("ipfs://0"), false));
+    assert!(res.main_failed());
+
+    let res = nft.send(
+        USERS[0],
+        set_metadata(Some("ipfs://media"), Some("ipfs://0"), false),
+    );
+    assert!(res.contains(&(
+        USERS[0],
+        Event::TokenMetadataSet {
+            token_id: 0.into(),
+            frozen: false,
+        }
+        .encode()
+    )));
+    let res = nft.send(USERS[0], Action::TokenURI(0.into()));
+    assert!(res.contains(&(USERS[0], Event::TokenURI(String::from("ipfs://0")).encode())));
+
+    let res = nft.send(USERS[0], set_metadata(Some("ipfs://media"), None, true));
+    assert!(!res.main_failed());
+    let res = nft.send(USERS[0], Action::TokenURI(0.into()));
+    assert!(res.contains(&(
+        USERS[0],
+        Event::TokenURI(String::from("ipfs://media")).encode()
+    )));
+
+    // must fail since the metadata is frozen
+    let res = nft.send(USERS[0], set_metadata(None, Some("ipfs://0"), false));
+    assert!(res.main_failed());
+
+    // must fail since the token doesn't exist
+    let res = nft.send(USERS[0], Action::TokenURI(1.into()));
+    assert!(res.main_failed());
+}
+
+#[test]
+fn soulbound_token() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let nft = sys.get_program(1);
+    let res = nft.send(USERS[0], Action::Mint);
+    assert!(!res.main_failed());
+    let res = nft.send(
+        USERS[0],
+        Action::Transfer {
+            to: USERS[1].into(),
+            token_id: 1.into(),
+        },
+    );
+    assert!(!res.main_failed());
+
+    // must fail since only the contract owner can make the token soulbound
+    let res = nft.send(
+        USERS[1],
+        Action::SetTransferable {
+            token_id: 1.into(),
+            transferable: Some(false),
+        },
+    );
+    assert!(res.main_failed());
+
+    let res = nft.send(
+        USERS[0],
+        Action::SetTransferable {
+            token_id: 1.into(),
+            transferable: Some(false),
+        },
+    );
+    assert!(res.contains(&(
+        USERS[0],
+        Event::TransferableSet {
+            token_id: 1.into(),
+            transferable: false,
+        }
+        .encode()
+    )));
+
+    // must fail since the token is not transferable
+    let res = nft.send(
+        USERS[1],
+        Action::Transfer {
+            to: USERS[2].into(),
+            token_id: 1.into(),
+        },
+    );
+    assert!(res.main_failed());
+    let res = nft.send(
+        USERS[1],
+        Action::Approve {
+            to: USERS[2].into(),
+            token_id: 1.into(),
+        },
+    );
+    assert!(res.main_failed());
+
+    // the other tokens are still transferable
+    let res = nft.send(
+        USERS[0],
+        Action::Transfer {
+            to: USERS[2].into(),
+            token_id: 0.into(),
+        },
+    );
+    assert!(!res.main_failed());
+
+    // must fail since only the contract owner can revoke tokens
+    let res = nft.send(USERS[2], Action::Revoke(1.into()));
+    assert!(res.main_failed());
+
+    let res = nft.send(USERS[0], Action::Revoke(1.into()));
+    assert!(res.contains(&(
+        USERS[0],
+        Event::Transfer {
+            from: USERS[1].into(),
+            to: 0.into(),
+            token_id: 1.into(),
+        }
+        .encode()
+    )));
+    let res = nft.send(USERS[0], Action::BalanceOf(USERS[1].into()));
+    assert!(res.contains(&(USERS[0], Event::BalanceOf(0.into()).encode())));
+}
+
+#[test]
+fn attributes() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let nft = sys.get_program(1);
+    let set_attribute = |value| Action::SetAttribute {
+        token_id: 0.into(),
+        key: String::from("level"),
+        value: AttributeValue::Int(value),
+    };
+
+    // must fail since the caller is not an attribute updater
+    let res = nft.send(USERS[1], set_attribute(1));
+    assert!(res.main_failed());
+
+    // must fail since only the contract owner can manage the attribute updaters
+    let res = nft.send(
+        USERS[1],
+        Action::SetAttributeUpdater {
+            account: USERS[1].into(),
+            allowed: true,
+        },
+    );
+    assert!(res.main_failed());
+
+    let res = nft.send(
+        USERS[0],
+        Action::SetAttributeUpdater {
+            account: USERS[1].into(),
+            allowed: true,
+        },
+    );
+    assert!(res.contains(&(
+        USERS[0],
+        Event::AttributeUpdaterSet {
+            account: USERS[1].into(),
+            allowed: true,
+        }
+        .encode()
+    )));
+
+    let res = nft.send(USERS[1], set_attribute(2));
+    assert!(res.contains(&(
+        USERS[1],
+        Event::AttributeSet {
+            token_id: 0.into(),
+            key: String::from("level"),
+            value: AttributeValue::Int(2),
+        }
+        .encode()
+    )));
+
+    let remove_attribute = |key: &str| Action::RemoveAttribute {
+        token_id: 0.into(),
+        key: String::from(key),
+    };
+    // must fail since the token has no such attribute
+    let res = nft.send(USERS[1], remove_attribute("class"));
+    assert!(res.main_failed());
+
+    let res = nft.send(USERS[1], remove_attribute("level"));
+    assert!(res.contains(&(
+        USERS[1],
+        Event::AttributeRemoved {
+            token_id: 0.into(),
+            key: String::from("level"),
+        }
+        .encode()
+    )));
+}
+
+#[test]
+fn burn_token_with_attributes() {
+    let sys = System::new();
+    init_with_mint(&sys);
+    let nft = sys.get_program(1);
+    let res = nft.send(
+        USERS[0],
+        Action::SetAttributeUpdater {
+            account: USERS[0].into(),
+            allowed: true,
+        },
+    );
+    assert!(!res.main_failed());
+    let res = nft.send(
+        USERS[0],
+        Action::SetAttribute {
+            token_id: 0.into(),
+            key: String::from("level"),
+            value: AttributeValue::Int(1),
+        },
+    );
+    assert!(!res.main_failed());
+    let res = nft.send(USERS[0], Action::TokenAttributes(0.into()));
+    assert!(res.contains(&(
+        USERS[0],
+        Event::TokenAttributes(BTreeMap::from([(
+            String::from("level"),
+            AttributeValue::Int(1)
+        )]))
+        .encode()
+    )));
+
+    let res = nft.send(USERS[0], Action::Burn(0.into()));
+    assert!(!res.main_failed());
+    // the attributes are removed with the burnt token
+    let res = nft.send(USERS[0], Action::TokenAttributes(0.into()));
+    assert!(res.contains(&(USERS[0], Event::TokenAttributes(BTreeMap::new()).encode())));
+}
@@ -26,6 +26,8 @@ pub struct Auction {
     pub ended_at: u64,
     pub current_price: u128,
     pub bids: Vec<Bid>,
+    /// The bid of at least this price settles the auction immediately
+    pub buy_now_price: Option<u128>,
 }
 
 #[derive(Debug, Encode, Decode, TypeInfo, Clone)]
@@ -34,6 +36,14 @@ pub struct Bid {
     pub price: u128,
 }
 
+#[derive(Debug, Encode, Decode, TypeInfo, Clone)]
+pub struct AuctionStatus {
+    pub auction: Auction,
+    pub highest_bid: Option<Bid>,
+    /// True if the auction is over and can be settled
+    pub ended: bool,
+}
+
 #[derive(Debug, Encode, Decode, TypeInfo, Clone, Default)]
 pub struct Item {
     pub owner_id: ActorId,
@@ -66,6 +76,18 @@ pub enum MarketAction {
         min_price: u128,
         bid_period: u64,
         duration: u64,
+        /// The auction is cancelled if the highest bid is less than that price, it isn't disclosed
+        reserve_price: Option<u128>,
+        buy_now_price: Option<u128>,
+    },
+    /// Withdraws the outbid bids of the caller in the indicated currency, `None` for the native value.
+    WithdrawRefunds {
+        ft_contract_id: Option<ActorId>,
+    },
+    /// Cancels the auction, only the item owner can cancel it before the first bid.
+    CancelAuction {
+        nft_contract_id: ActorId,
+        token_id: U256,
     },
     AddBid {
         nft_contract_id: ActorId,
@@ -96,6 +118,82 @@ pub enum MarketAction {
         nft_contract_id: ActorId,
         token_id: U256,
     },
+    /// Changes the price of the listed item, only the item owner can update it.
+    UpdatePrice {
+        nft_contract_id: ActorId,
+        token_id: U256,
+        price: u128,
+    },
+    /// Takes the item off the sale keeping its offers, only the item owner can cancel the listing.
+    CancelListing {
+        nft_contract_id: ActorId,
+        token_id: U256,
+    },
+    /// Removes the item from the marketplace, revoking the marketplace approval
+    /// and crediting the offers to refunds. Only the item owner can delist it.
+    Delist {
+        nft_contract_id: ActorId,
+        token_id: U256,
+    },
+    /// Sent by an NFT contract when a token is safely transferred to the marketplace.
+    /// Only tokens of approved NFT contracts are accepted.
+    #[codec(index = 255)]
+    OnNftReceived {
+        operator: ActorId,
+        from: ActorId,
+        token_id: U256,
+        data: Vec<u8>,
+    },
+}
+
+#[derive(Debug, Encode, Decode, TypeInfo)]
+pub enum MarketState {
+    Item {
+        nft_contract_id: ActorId,
+        token_id: U256,
+    },
+    Offers {
+        nft_contract_id: ActorId,
+        token_id: U256,
+    },
+    AuctionStatus {
+        nft_contract_id: ActorId,
+        token_id: U256,
+    },
+    ApprovedNftContracts,
+    ApprovedFtContracts,
+    Treasury,
+    /// Returns the outbid bids the account can withdraw in the currency
+    Refunds {
+        account: ActorId,
+        ft_contract_id: Option<ActorId>,
+    },
+    ItemsByOwner(ActorId),
+    /// Returns at most `limit` items of the NFT contract ordered by the token ID, skipping the first `offset`
+    ItemsByCollection {
+        nft_contract_id: ActorId,
+        offset: u32,
+        limit: u32,
+    },
+    /// Returns the items with an auction that is not settled yet
+    ActiveAuctions,
+}
+
+#[derive(Debug, Encode, Decode, TypeInfo)]
+pub enum MarketStateReply {
+    Item(Option<Item>),
+    Offers(Vec<Offer>),
+    /// `None` if there is no auction for the item
+    AuctionStatus(Option<AuctionStatus>),
+    ContractIds(Vec<ActorId>),
+    Treasury {
+        owner_id: ActorId,
+        treasury_id: ActorId,
+        /// The fee in hundredths of a percent
+        treasury_fee: u128,
+    },
+    Refunds(u128),
+    Items(Vec<Item>),
 }
 
 #[derive(Debug, Encode, Decode, TypeInfo)]
@@ -154,4 +252,37 @@ pub enum MarketEvent {
         token_id: U256,
         price: u128,
     },
+    PriceUpdated {
+        nft_contract_id: ActorId,
+        token_id: U256,
+        price: u128,
+    },
+    ListingCancelled {
+        nft_contract_id: ActorId,
+        token_id: U256,
+    },
+    ItemDelisted {
+        nft_contract_id: ActorId,
+        token_id: U256,
+    },
+    /// The auction got a higher bid, was settled or cancelled while the bid was being paid,
+    /// so the bid price is credited to the refunds of the bidder
+    BidRejected {
+        nft_contract_id: ActorId,
+        token_id: U256,
+        price: u128,
+    },
+    RefundsWithdrawn {
+        ft_contract_id: Option<ActorId>,
+        amount: u128,
+    },
+    /// The FT contract rejected the transfer, so the refunds are left to withdraw
+    RefundsWithdrawalFailed {
+        ft_contract_id: Option<ActorId>,
+        amount: u128,
+    },
+    #[codec(index = 255)]
+    NftReceived {
+        token_id: U256,
+    },
 }
@@ -1,11 +1,12 @@
 use crate::{
+    change_item_owner,
     nft_messages::{nft_approve, nft_payouts, nft_transfer},
-    payment::{check_attached_value, transfer_payment},
-    Item, Market, MarketEvent,
+    payment::{check_attached_value, transfer_payment, transfer_tokens},
+    ContractAndTokenId, Item, Market, MarketEvent,
 };
 use gstd::{exec, msg, prelude::*, ActorId};
 use market_io::*;
-use primitive_types::{H256, U256};
+use primitive_types::U256;
 const MIN_BID_PERIOD: u64 = 60_000;
 
 impl Market {
@@ -21,6 +22,10 @@ impl Market {
     /// * `token_id`: the NFT id
     /// * `min_price`: the starting price
     /// * `bid_period`: the time that the auction lasts until another bid occurs
+    /// * `duration`: the time that the auction lasts if there are no late bids
+    /// * `reserve_price`: the hidden price below which the auction is cancelled
+    /// * `buy_now_price`: the price at which a bid settles the auction immediately
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_auction(
         &mut self,
         nft_contract_id: &ActorId,
@@ -29,11 +34,12 @@ impl Market {
         min_price: u128,
         bid_period: u64,
         duration: u64,
+        reserve_price: Option<u128>,
+        buy_now_price: Option<u128>,
     ) {
         self.check_approved_nft_contract(nft_contract_id);
         self.check_approved_ft_contract(ft_contract_id);
-        let contract_and_token_id =
-            format!("{}{}", H256::from_slice(nft_contract_id.as_ref()), token_id);
+        let contract_and_token_id = (*nft_contract_id, token_id);
         self.on_auction(&contract_and_token_id);
         if bid_period < MIN_BID_PERIOD || duration < MIN_BID_PERIOD {
             panic!("bid period or auction duration can't be less than 1 minute");
@@ -41,6 +47,14 @@ impl Market {
         if min_price == 0 {
             panic!("price can't be equal to zero");
         }
+        if let Some(buy_now_price) = buy_now_price {
+            if buy_now_price <= min_price {
+                panic!("buy-now price must be greater than the min price");
+            }
+            if reserve_price.map_or(false, |reserve_price| reserve_price > buy_now_price) {
+                panic!("reserve price can't be greater than the buy-now price");
+            }
+        }
         // approve nft to trade on the marketplace
         nft_approve(nft_contract_id, &exec::program_id(), token_id).await;
 
@@ -50,8 +64,19 @@ impl Market {
             ended_at: exec::block_timestamp() + duration,
             current_price: min_price,
             bids: vec![],
+            buy_now_price,
         };
-        self.items
+        match reserve_price {
+            Some(reserve_price) => {
+                self.reserve_prices
+                    .insert(contract_and_token_id, reserve_price);
+            }
+            None => {
+                self.reserve_prices.remove(&contract_and_token_id);
+            }
+        }
+        let item = self
+            .items
             .entry(contract_and_token_id)
             .and_modify(|item| {
                 item.price = None;
@@ -67,6 +92,10 @@ impl Market {
                 auction: Some(auction),
                 offers: Vec::new(),
             });
+        self.items_by_owner
+            .entry(item.owner_id)
+            .or_default()
+            .insert(contract_and_token_id);
 
         msg::reply(
             MarketEvent::AuctionCreated {
@@ -79,15 +108,51 @@ impl Market {
         .unwrap();
     }
 
+    /// Cancels the auction before the first bid
+    /// Requirements:
+    /// * Only the item owner can cancel the auction
+    /// * There must be no bids
+    /// Arguments:
+    /// * `nft_contract_id`: the NFT contract address
+    /// * `token_id`: the NFT id
+    pub fn cancel_auction(&mut self, nft_contract_id: &ActorId, token_id: U256) {
+        let contract_and_token_id = (*nft_contract_id, token_id);
+        let item = self
+            .items
+            .get_mut(&contract_and_token_id)
+            .expect("Item does not exist");
+        if item.owner_id != msg::source() {
+            panic!("Only the item owner can cancel the auction");
+        }
+        let auction = item.auction.as_ref().expect("Auction doesn not exist");
+        if !auction.bids.is_empty() {
+            panic!("Can't cancel the auction with bids");
+        }
+        item.auction = None;
+        self.reserve_prices.remove(&contract_and_token_id);
+
+        msg::reply(
+            MarketEvent::AuctionCancelled {
+                nft_contract_id: *nft_contract_id,
+                token_id,
+            },
+            0,
+        )
+        .unwrap();
+    }
+
     /// Settles the auction
+    /// If the highest bid doesn't reach the reserve price, the auction is cancelled
+    /// and the highest bid is credited to the refunds of the bidder
+    /// A failed payout to the treasury or to a royalty recipient is credited to its refunds,
+    /// so the settlement can't be blocked by the payment token
     /// Requirements:
     /// * The auction must be over
     /// Arguments:
     /// * `nft_contract_id`: the NFT contract address
     /// * `token_id`: the NFT id
     pub async fn settle_auction(&mut self, nft_contract_id: &ActorId, token_id: U256) {
-        let contract_and_token_id =
-            format!("{}{}", H256::from_slice(nft_contract_id.as_ref()), token_id);
+        let contract_and_token_id = (*nft_contract_id, token_id);
         let item = self
             .items
             .get_mut(&contract_and_token_id)
@@ -99,9 +164,19 @@ impl Market {
             panic!("Auction is not over");
         }
         item.auction = None;
-        let mut bids = auction.bids;
+        let bids = auction.bids;
+        let reserve_price = self
+            .reserve_prices
+            .remove(&contract_and_token_id)
+            .unwrap_or_default();
 
-        if bids.is_empty() {
+        if bids.last().map_or(true, |bid| bid.price < reserve_price) {
+            if let Some(bid) = bids.last() {
+                *self
+                    .refunds
+                    .entry((bid.id, item.ft_contract_id))
+                    .or_default() += bid.price;
+            }
             msg::reply(
                 MarketEvent::AuctionCancelled {
                     nft_contract_id: *nft_contract_id,
@@ -114,38 +189,35 @@ impl Market {
             return;
         }
 
-        let highest_bid = &bids[bids.len() - 1].clone();
-        bids.pop();
-        for bid in bids {
-            transfer_payment(&exec::program_id(), &bid.id, item.ft_contract_id, bid.price).await;
-        }
+        // the outbid bidders were credited their refunds when they were outbid
+        let (winner, price) = (bids[bids.len() - 1].id, bids[bids.len() - 1].price);
+        let (owner_id, ft_contract_id) = (item.owner_id, item.ft_contract_id);
         // fee for treasury
-        let treasury_fee = highest_bid.price * self.treasury_fee / 10_000u128;
-        transfer_payment(
-            &exec::program_id(),
-            &self.treasury_id,
-            item.ft_contract_id,
-            treasury_fee,
-        )
-        .await;
-        let payouts = nft_payouts(
-            nft_contract_id,
-            &item.owner_id,
-            highest_bid.price - treasury_fee,
-        )
-        .await;
+        let treasury_fee = price * self.treasury_fee / 10_000u128;
+        let treasury_id = self.treasury_id;
+        self.pay_out(&treasury_id, ft_contract_id, treasury_fee)
+            .await;
+        let payouts = nft_payouts(nft_contract_id, &owner_id, token_id, price - treasury_fee).await;
         for (account, amount) in payouts.iter() {
-            transfer_payment(&exec::program_id(), account, item.ft_contract_id, *amount).await;
+            self.pay_out(account, ft_contract_id, *amount).await;
         }
 
-        item.owner_id = highest_bid.id;
+        change_item_owner(
+            &mut self.items_by_owner,
+            contract_and_token_id,
+            &owner_id,
+            &winner,
+        );
+        if let Some(item) = self.items.get_mut(&contract_and_token_id) {
+            item.owner_id = winner;
+        }
         // transfer NFT
-        nft_transfer(nft_contract_id, &highest_bid.id, token_id).await;
+        nft_transfer(nft_contract_id, &winner, token_id).await;
         msg::reply(
             MarketEvent::AuctionSettled {
                 nft_contract_id: *nft_contract_id,
                 token_id,
-                price: highest_bid.price,
+                price,
             },
             0,
         )
@@ -153,6 +225,11 @@ impl Market {
     }
 
     /// Adds a bid to an ongoing auction
+    /// A bid during the last `bid_period` extends the auction by `bid_period` from the bid,
+    /// and a bid reaching the buy-now price settles the auction immediately
+    /// The outbid bid is credited to the refunds of its bidder, see `withdraw_refunds`
+    /// If the auction is outbid, closed or changed while waiting for the payment,
+    /// the bid is rejected and credited to the refunds of the caller
     /// Requirements:
     /// * The auction must be on
     /// * The caller must have enough balance for the offered price
@@ -161,32 +238,60 @@ impl Market {
     /// * `token_id`: the NFT id
     /// * `price`: the offered price
     pub async fn add_bid(&mut self, nft_contract_id: &ActorId, token_id: U256, price: u128) {
-        let contract_and_token_id =
-            format!("{}{}", H256::from_slice(nft_contract_id.as_ref()), token_id);
+        let contract_and_token_id = (*nft_contract_id, token_id);
 
         let item = self
             .items
-            .get_mut(&contract_and_token_id)
+            .get(&contract_and_token_id)
             .expect("Item does not exist");
 
-        let mut auction = item.auction.clone().expect("Auction doesn not exist");
+        let auction = item.auction.as_ref().expect("Auction doesn not exist");
         if auction.ended_at < exec::block_timestamp() {
             panic!("Auction has already ended");
         }
 
-        check_attached_value(item.ft_contract_id, price);
+        let ft_contract_id = item.ft_contract_id;
+        check_attached_value(ft_contract_id, price);
 
         if price <= auction.current_price {
             panic!("Cant offer less or equal to the current bid price")
         }
 
-        transfer_payment(
-            &msg::source(),
-            &exec::program_id(),
-            item.ft_contract_id,
-            price,
-        )
-        .await;
+        transfer_payment(&msg::source(), &exec::program_id(), ft_contract_id, price).await;
+
+        // the auction is checked again since it could get a higher bid, be settled or cancelled
+        // while waiting for the payment, and panicking now wouldn't give the payment back
+        let auction = match self.items.get_mut(&contract_and_token_id) {
+            Some(Item {
+                ft_contract_id: item_ft_contract_id,
+                auction: Some(auction),
+                ..
+            }) if *item_ft_contract_id == ft_contract_id
+                && price > auction.current_price
+                && auction.ended_at >= exec::block_timestamp() =>
+            {
+                auction
+            }
+            _ => {
+                *self
+                    .refunds
+                    .entry((msg::source(), ft_contract_id))
+                    .or_default() += price;
+                msg::reply(
+                    MarketEvent::BidRejected {
+                        nft_contract_id: *nft_contract_id,
+                        token_id,
+                        price,
+                    },
+                    0,
+                )
+                .unwrap();
+                return;
+            }
+        };
+        if let Some(outbid) = auction.bids.last() {
+            *self.refunds.entry((outbid.id, ft_contract_id)).or_default() += outbid.price;
+        }
         auction.bids.push(Bid {
             id: msg::source(),
             price,
@@ -195,7 +300,14 @@ impl Market {
             auction.ended_at = exec::block_timestamp() + auction.bid_period;
         }
         auction.current_price = price;
-        item.auction = Some(auction);
+        if auction
+            .buy_now_price
+            .map_or(false, |buy_now_price| price >= buy_now_price)
+        {
+            auction.ended_at = exec::block_timestamp();
+            self.settle_auction(nft_contract_id, token_id).await;
+            return;
+        }
         msg::reply(
             MarketEvent::BidAdded {
                 nft_contract_id: *nft_contract_id,
@@ -207,8 +319,103 @@ impl Market {
         .unwrap();
     }
 
+    /// Withdraws the refunds of the outbid bids and of the offers for delisted items
+    /// If the FT transfer fails, the refunds are kept for a later withdrawal
+    /// A withdrawal whose transfer was sent but not completed is retried with the same `tx_id`,
+    /// so the token program doesn't transfer the refunds twice
+    /// Requirements:
+    /// * The caller must have refunds in the indicated currency
+    /// Arguments:
+    /// * `ft_contract_id`: the FT contract address, `None` for the native value
+    pub async fn withdraw_refunds(&mut self, ft_contract_id: Option<ActorId>) {
+        let key = (msg::source(), ft_contract_id);
+        let contract_id = match ft_contract_id {
+            Some(contract_id) => contract_id,
+            None => {
+                let amount = self.refunds.remove(&key).expect("There are no refunds");
+                msg::send(msg::source(), "", amount).unwrap();
+                msg::reply(
+                    MarketEvent::RefundsWithdrawn {
+                        ft_contract_id,
+                        amount,
+                    },
+                    0,
+                )
+                .unwrap();
+                return;
+            }
+        };
+
+        // the refunds are moved to the pending withdrawals before the transfer,
+        // so they can't be withdrawn twice and the withdrawal survives a failed reply
+        let pending = match self.pending_withdrawals.get(&key) {
+            Some(pending) => *pending,
+            None => {
+                let amount = self.refunds.remove(&key).expect("There are no refunds");
+                let pending = (self.transaction_id, amount);
+                self.transaction_id += 1;
+                self.pending_withdrawals.insert(key, pending);
+                pending
+            }
+        };
+        let (tx_id, amount) = pending;
+        let result = transfer_tokens(
+            &contract_id,
+            &exec::program_id(),
+            &msg::source(),
+            amount,
+            Some(tx_id),
+        )
+        .await;
+
+        // another message may have completed the same withdrawal while waiting for the reply
+        let still_pending = self.pending_withdrawals.get(&key) == Some(&pending);
+        if still_pending {
+            self.pending_withdrawals.remove(&key);
+        }
+        if result.is_err() {
+            // the transfer is failed, so the refunds are given back instead of panicking,
+            // since a panic after the await would not restore them
+            if still_pending {
+                *self.refunds.entry(key).or_default() += amount;
+            }
+            msg::reply(
+                MarketEvent::RefundsWithdrawalFailed {
+                    ft_contract_id,
+                    amount,
+                },
+                0,
+            )
+            .unwrap();
+            return;
+        }
+        msg::reply(
+            MarketEvent::RefundsWithdrawn {
+                ft_contract_id,
+                amount,
+            },
+            0,
+        )
+        .unwrap();
+    }
+
+    // pays out the auction proceeds, a failed FT transfer is credited to the account refunds,
+    // since the auction is already closed and a panic after the await would strand the payment
+    async fn pay_out(&mut self, account: &ActorId, ft_contract_id: Option<ActorId>, amount: u128) {
+        if let Some(contract_id) = ft_contract_id {
+            if transfer_tokens(&contract_id, &exec::program_id(), account, amount, None)
+                .await
+                .is_err()
+            {
+                *self.refunds.entry((*account, ft_contract_id)).or_default() += amount;
+            }
+        } else {
+            transfer_payment(&exec::program_id(), account, None, amount).await;
+        }
+    }
+
     // checks that there is an active auction
-    pub fn on_auction(&self, contract_and_token_id: &String) {
+    pub fn on_auction(&self, contract_and_token_id: &ContractAndTokenId) {
         if let Some(item) = self.items.get(contract_and_token_id) {
             if item.auction.is_some() {
                 panic!("There is an opened auction");
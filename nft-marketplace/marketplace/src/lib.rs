@@ -3,7 +3,7 @@
 use codec::{Decode, Encode};
 use gstd::{exec, msg, prelude::*, ActorId};
 pub use market_io::*;
-use primitive_types::{H256, U256};
+use primitive_types::U256;
 use scale_info::TypeInfo;
 pub mod nft_messages;
 use nft_messages::*;
@@ -12,14 +12,26 @@ pub mod offers;
 pub mod payment;
 pub mod sale;
 
-pub type ContractAndTokenId = String;
+/// The NFT contract address and the token ID of a market item
+pub type ContractAndTokenId = (ActorId, U256);
 
 #[derive(Debug, Default, Encode, Decode, TypeInfo)]
 pub struct Market {
     pub owner_id: ActorId,
     pub treasury_id: ActorId,
     pub treasury_fee: u128,
+    /// The items are ordered by their NFT contract, so the items of a collection make a range
     pub items: BTreeMap<ContractAndTokenId, Item>,
+    pub items_by_owner: BTreeMap<ActorId, BTreeSet<ContractAndTokenId>>,
+    /// The reserve prices of the auctions, kept apart from the items to stay hidden
+    pub reserve_prices: BTreeMap<ContractAndTokenId, u128>,
+    /// The outbid bids and the offers for delisted items to withdraw by the bidders,
+    /// and the currencies of the bids
+    pub refunds: BTreeMap<(ActorId, Option<ActorId>), u128>,
+    /// The `tx_id` and the amount of the FT refund withdrawals being transferred
+    pub pending_withdrawals: BTreeMap<(ActorId, Option<ActorId>), (u64, u128)>,
+    /// The next `tx_id` of the FT transfers
+    pub transaction_id: u64,
     pub approved_nft_contracts: Vec<ActorId>,
     pub approved_ft_contracts: Vec<ActorId>,
     pub offer_history_length: u8,
@@ -67,13 +79,13 @@ impl Market {
     ) {
         self.check_approved_nft_contract(nft_contract_id);
         self.check_approved_ft_contract(ft_contract_id);
-        let contract_and_token_id =
-            format!("{}{}", H256::from_slice(nft_contract_id.as_ref()), token_id);
+        let contract_and_token_id = (*nft_contract_id, token_id);
         self.on_auction(&contract_and_token_id);
 
         nft_approve(nft_contract_id, &exec::program_id(), token_id).await;
 
-        self.items
+        let item = self
+            .items
             .entry(contract_and_token_id)
             .and_modify(|item| {
                 item.price = price;
@@ -88,6 +100,10 @@ impl Market {
                 auction: None,
                 offers: Vec::new(),
             });
+        self.items_by_owner
+            .entry(item.owner_id)
+            .or_default()
+            .insert(contract_and_token_id);
 
         msg::reply(
             MarketEvent::MarketDataAdded {
@@ -101,6 +117,62 @@ impl Market {
         .unwrap();
     }
 
+    /// Accepts an NFT safely transferred to the marketplace
+    /// The NFT is kept as the item of its previous owner, who can then list or delist it
+    /// Requirements:
+    /// * `msg::source()` must be an approved NFT contract
+    /// Arguments:
+    /// * `from`: the previous NFT owner
+    /// * `token_id`: the NFT id
+    fn on_nft_received(&mut self, from: &ActorId, token_id: U256) {
+        self.check_approved_nft_contract(&msg::source());
+        let contract_and_token_id = (msg::source(), token_id);
+        let item = self
+            .items
+            .entry(contract_and_token_id)
+            .or_insert_with(|| Item {
+                owner_id: *from,
+                nft_contract_id: msg::source(),
+                ft_contract_id: None,
+                token_id,
+                price: None,
+                auction: None,
+                offers: Vec::new(),
+            });
+        change_item_owner(
+            &mut self.items_by_owner,
+            contract_and_token_id,
+            &item.owner_id,
+            from,
+        );
+        item.owner_id = *from;
+
+        msg::reply(MarketEvent::NftReceived { token_id }, 0).unwrap();
+    }
+
+    /// Returns the items listed by `owner`
+    pub fn items_by_owner(&self, owner: &ActorId) -> Vec<Item> {
+        self.items_by_owner
+            .get(owner)
+            .map(|keys| keys.iter().map(|key| self.items[key].clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns at most `limit` items of the NFT contract ordered by the token ID, skipping the first `offset`
+    pub fn items_by_collection(
+        &self,
+        nft_contract_id: &ActorId,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<Item> {
+        self.items
+            .range((*nft_contract_id, U256::zero())..=(*nft_contract_id, U256::MAX))
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|(_, item)| item.clone())
+            .collect()
+    }
+
     pub fn check_owner(&self) {
         if msg::source() != self.owner_id {
             panic!("Only owner can make that action");
@@ -131,6 +203,9 @@ gstd::metadata! {
         handle:
             input: MarketAction,
             output: MarketEvent,
+        state:
+            input: MarketState,
+            output: MarketStateReply,
 }
 
 #[gstd::async_main]
@@ -164,11 +239,9 @@ async unsafe fn main() {
             nft_contract_id,
             token_id,
         } => {
-            let contract_and_token_id =
-                format!("{}{}", H256::from_slice(nft_contract_id.as_ref()), token_id);
             let item = market
                 .items
-                .get(&contract_and_token_id)
+                .get(&(nft_contract_id, token_id))
                 .unwrap_or(&Item::default())
                 .clone();
             msg::reply(MarketEvent::ItemInfo(item), 0).unwrap();
@@ -204,6 +277,8 @@ async unsafe fn main() {
             min_price,
             bid_period,
             duration,
+            reserve_price,
+            buy_now_price,
         } => {
             market
                 .create_auction(
@@ -213,9 +288,18 @@ async unsafe fn main() {
                     min_price,
                     bid_period,
                     duration,
+                    reserve_price,
+                    buy_now_price,
                 )
                 .await;
         }
+        MarketAction::WithdrawRefunds { ft_contract_id } => {
+            market.withdraw_refunds(ft_contract_id).await
+        }
+        MarketAction::CancelAuction {
+            nft_contract_id,
+            token_id,
+        } => market.cancel_auction(&nft_contract_id, token_id),
         MarketAction::AddBid {
             nft_contract_id,
             token_id,
@@ -228,6 +312,22 @@ async unsafe fn main() {
         } => {
             market.settle_auction(&nft_contract_id, token_id).await;
         }
+        MarketAction::UpdatePrice {
+            nft_contract_id,
+            token_id,
+            price,
+        } => market.update_price(&nft_contract_id, token_id, price),
+        MarketAction::CancelListing {
+            nft_contract_id,
+            token_id,
+        } => market.cancel_listing(&nft_contract_id, token_id),
+        MarketAction::Delist {
+            nft_contract_id,
+            token_id,
+        } => market.delist(&nft_contract_id, token_id).await,
+        MarketAction::OnNftReceived { from, token_id, .. } => {
+            market.on_nft_received(&from, token_id);
+        }
     }
 }
 
@@ -242,3 +342,92 @@ pub unsafe extern "C" fn init() {
     };
     MARKET = Some(market);
 }
+
+/// Moves the item from the items of `from` to the items of `to`
+pub fn change_item_owner(
+    items_by_owner: &mut BTreeMap<ActorId, BTreeSet<ContractAndTokenId>>,
+    contract_and_token_id: ContractAndTokenId,
+    from: &ActorId,
+    to: &ActorId,
+) {
+    if let Some(keys) = items_by_owner.get_mut(from) {
+        keys.remove(&contract_and_token_id);
+        if keys.is_empty() {
+            items_by_owner.remove(from);
+        }
+    }
+    items_by_owner
+        .entry(*to)
+        .or_default()
+        .insert(contract_and_token_id);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn meta_state() -> *mut [i32; 2] {
+    let query: MarketState = msg::load().expect("failed to decode input argument");
+    let market: &mut Market = MARKET.get_or_insert(Market::default());
+    let encoded = match query {
+        MarketState::Item {
+            nft_contract_id,
+            token_id,
+        } => MarketStateReply::Item(market.items.get(&(nft_contract_id, token_id)).cloned()),
+        MarketState::Offers {
+            nft_contract_id,
+            token_id,
+        } => MarketStateReply::Offers(
+            market
+                .items
+                .get(&(nft_contract_id, token_id))
+                .map(|item| item.offers.clone())
+                .unwrap_or_default(),
+        ),
+        MarketState::AuctionStatus {
+            nft_contract_id,
+            token_id,
+        } => MarketStateReply::AuctionStatus(
+            market
+                .items
+                .get(&(nft_contract_id, token_id))
+                .and_then(|item| item.auction.clone())
+                .map(|auction| AuctionStatus {
+                    highest_bid: auction.bids.last().cloned(),
+                    ended: auction.ended_at <= exec::block_timestamp(),
+                    auction,
+                }),
+        ),
+        MarketState::ApprovedNftContracts => {
+            MarketStateReply::ContractIds(market.approved_nft_contracts.clone())
+        }
+        MarketState::ApprovedFtContracts => {
+            MarketStateReply::ContractIds(market.approved_ft_contracts.clone())
+        }
+        MarketState::Treasury => MarketStateReply::Treasury {
+            owner_id: market.owner_id,
+            treasury_id: market.treasury_id,
+            treasury_fee: market.treasury_fee,
+        },
+        MarketState::Refunds {
+            account,
+            ft_contract_id,
+        } => {
+            MarketStateReply::Refunds(*market.refunds.get(&(account, ft_contract_id)).unwrap_or(&0))
+        }
+        MarketState::ItemsByOwner(owner) => MarketStateReply::Items(market.items_by_owner(&owner)),
+        MarketState::ItemsByCollection {
+            nft_contract_id,
+            offset,
+            limit,
+        } => MarketStateReply::Items(market.items_by_collection(&nft_contract_id, offset, limit)),
+        MarketState::ActiveAuctions => MarketStateReply::Items(
+            market
+                .items
+                .values()
+                .filter(|item| item.auction.is_some())
+                .cloned()
+                .collect(),
+        ),
+    }
+    .encode();
+
+    gstd::util::to_leak_ptr(encoded)
+}
@@ -22,11 +22,38 @@ pub async fn nft_approve(nft_program_id: &ActorId, to: &ActorId, token_id: U256)
             .expect("error in transfer");
 }
 
-pub async fn nft_payouts(nft_program_id: &ActorId, owner: &ActorId, amount: u128) -> Payout {
+/// Revokes the marketplace approval of the token
+/// The result is ignored since the token may already be transferred or burned
+pub async fn nft_revoke_approval(nft_program_id: &ActorId, token_id: U256) {
+    let _revoke_response: Result<NFTEvent, _> =
+        msg::send_and_wait_for_reply(*nft_program_id, NFTAction::RevokeApproval(token_id), 0)
+            .unwrap()
+            .await;
+}
+
+pub async fn nft_owner_of(nft_program_id: &ActorId, token_id: U256) -> ActorId {
+    let owner_response: NFTEvent =
+        msg::send_and_wait_for_reply(*nft_program_id, NFTAction::OwnerOf(token_id), 0)
+            .unwrap()
+            .await
+            .expect("Error in function 'nft_owner_of' call");
+    match owner_response {
+        NFTEvent::OwnerOf(owner) => owner,
+        _ => panic!("Unexpected reply to 'OwnerOf'"),
+    }
+}
+
+pub async fn nft_payouts(
+    nft_program_id: &ActorId,
+    owner: &ActorId,
+    token_id: U256,
+    amount: u128,
+) -> Payout {
     let payouts: NFTEvent = msg::send_and_wait_for_reply(
         *nft_program_id,
         NFTAction::NFTPayout {
             owner: *owner,
+            token_id,
             amount,
         },
         0,
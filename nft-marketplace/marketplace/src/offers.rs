@@ -1,4 +1,5 @@
 use crate::{
+    change_item_owner,
     nft_messages::{nft_payouts, nft_transfer},
     payment::{check_attached_value, transfer_payment},
     Market,
@@ -34,8 +35,7 @@ impl Market {
         token_id: U256,
         price: u128,
     ) {
-        let contract_and_token_id =
-            format!("{}{}", H256::from_slice(nft_contract_id.as_ref()), token_id);
+        let contract_and_token_id = (*nft_contract_id, token_id);
         self.check_approved_ft_contract(ft_contract_id);
         self.on_auction(&contract_and_token_id);
         let item = self
@@ -91,8 +91,7 @@ impl Market {
         token_id: U256,
         offer_hash: H256,
     ) {
-        let contract_and_token_id =
-            format!("{}{}", H256::from_slice(nft_contract_id.as_ref()), token_id);
+        let contract_and_token_id = (*nft_contract_id, token_id);
         self.on_auction(&contract_and_token_id);
         let item = self
             .items
@@ -111,8 +110,13 @@ impl Market {
                 treasury_fee,
             )
             .await;
-            let payouts =
-                nft_payouts(nft_contract_id, &item.owner_id, offer.price - treasury_fee).await;
+            let payouts = nft_payouts(
+                nft_contract_id,
+                &item.owner_id,
+                token_id,
+                offer.price - treasury_fee,
+            )
+            .await;
             for (account, amount) in payouts.iter() {
                 transfer_payment(&exec::program_id(), account, offer.ft_contract_id, *amount).await;
             }
@@ -120,6 +124,12 @@ impl Market {
             offers.retain(|offer| offer.hash != offer_hash);
             item.offers = offers;
             item.price = None;
+            change_item_owner(
+                &mut self.items_by_owner,
+                contract_and_token_id,
+                &item.owner_id,
+                &offer.id,
+            );
             item.owner_id = offer.id;
             msg::reply(
                 MarketEvent::OfferAccepted {
@@ -145,8 +155,7 @@ impl Market {
     /// * `token_id`: the NFT id
     /// * `offer_hash`: the offer hash
     pub async fn withdraw(&mut self, nft_contract_id: &ActorId, token_id: U256, offer_hash: H256) {
-        let contract_and_token_id =
-            format!("{}{}", H256::from_slice(nft_contract_id.as_ref()), token_id);
+        let contract_and_token_id = (*nft_contract_id, token_id);
         let item = self
             .items
             .get_mut(&contract_and_token_id)
@@ -1,19 +1,36 @@
 use ft_io::*;
 use gstd::{exec, msg, ActorId};
 
-pub async fn transfer_tokens(contract_id: &ActorId, from: &ActorId, to: &ActorId, amount: u128) {
-    let _transfer_response: FTEvent = msg::send_and_wait_for_reply(
-        *contract_id,
+pub async fn transfer_tokens(
+    contract_id: &ActorId,
+    from: &ActorId,
+    to: &ActorId,
+    amount: u128,
+    tx_id: Option<u64>,
+) -> Result<(), FTError> {
+    let action = if from == &exec::program_id() {
         FTAction::Transfer {
+            to: *to,
+            amount,
+            tx_id,
+        }
+    } else {
+        FTAction::TransferFrom {
             from: *from,
             to: *to,
             amount,
-        },
-        0,
-    )
-    .unwrap()
-    .await
-    .expect("Error in transfer");
+            tx_id,
+        }
+    };
+    let transfer_response: FTEvent = msg::send_and_wait_for_reply(*contract_id, action, 0)
+        .unwrap()
+        .await
+        .expect("Error in decoding reply");
+    match transfer_response {
+        FTEvent::Transfer { .. } | FTEvent::TransferFrom { .. } => Ok(()),
+        FTEvent::Err(error) => Err(error),
+        _ => panic!("Unexpected reply to a transfer"),
+    }
 }
 
 pub async fn transfer_payment(
@@ -27,7 +44,20 @@ pub async fn transfer_payment(
             msg::send(*to, "", price).unwrap();
         }
     } else {
-        transfer_tokens(&ft_contract_id.unwrap(), from, to, price).await;
+        match transfer_tokens(&ft_contract_id.unwrap(), from, to, price, None).await {
+            Ok(()) => {}
+            Err(FTError::InsufficientBalance) => {
+                panic!("{from:?} doesn't have enough tokens for the payment")
+            }
+            Err(FTError::InsufficientAllowance) => {
+                panic!("{from:?} must approve the marketplace to spend the payment")
+            }
+            Err(FTError::Paused) => panic!("The payment token is paused"),
+            Err(FTError::AccountFrozen(account)) => {
+                panic!("{account:?} is frozen in the payment token")
+            }
+            Err(error) => panic!("Error in transfer from {from:?} to {to:?}: {error:?}"),
+        }
     }
 }
 
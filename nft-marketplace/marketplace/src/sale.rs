@@ -1,10 +1,11 @@
 use crate::{
-    nft_messages::{nft_payouts, nft_transfer},
+    change_item_owner,
+    nft_messages::{nft_owner_of, nft_payouts, nft_revoke_approval, nft_transfer},
     payment::{check_attached_value, transfer_payment},
-    Market, MarketEvent,
+    ContractAndTokenId, Item, Market, MarketEvent,
 };
-use gstd::{msg, prelude::*, ActorId};
-use primitive_types::{H256, U256};
+use gstd::{exec, msg, prelude::*, ActorId};
+use primitive_types::U256;
 
 impl Market {
     /// Called when a user wants to buy NFT.
@@ -16,8 +17,7 @@ impl Market {
     /// * `nft_contract_id`: NFT contract address
     /// * `token_id`: the token ID
     pub async fn buy_item(&mut self, nft_contract_id: &ActorId, token_id: U256) {
-        let contract_and_token_id =
-            format!("{}{}", H256::from_slice(nft_contract_id.as_ref()), token_id);
+        let contract_and_token_id = (*nft_contract_id, token_id);
         let item = self
             .items
             .get_mut(&contract_and_token_id)
@@ -34,6 +34,7 @@ impl Market {
         let payouts = nft_payouts(
             nft_contract_id,
             &item.owner_id,
+            token_id,
             item.price.unwrap() - treasury_fee,
         )
         .await;
@@ -51,6 +52,12 @@ impl Market {
         // transfer NFT to buyer
         nft_transfer(nft_contract_id, &msg::source(), token_id).await;
 
+        change_item_owner(
+            &mut self.items_by_owner,
+            contract_and_token_id,
+            &item.owner_id,
+            &msg::source(),
+        );
         item.owner_id = msg::source();
         item.price = None;
 
@@ -64,4 +71,113 @@ impl Market {
         )
         .unwrap();
     }
+
+    /// Changes the price of the listed item
+    /// Requirements:
+    /// * Only the item owner can update the price
+    /// * There must be no opened auction
+    /// Arguments:
+    /// * `nft_contract_id`: NFT contract address
+    /// * `token_id`: the token ID
+    /// * `price`: the new price
+    pub fn update_price(&mut self, nft_contract_id: &ActorId, token_id: U256, price: u128) {
+        let contract_and_token_id = (*nft_contract_id, token_id);
+        self.on_auction(&contract_and_token_id);
+        let item = self.owned_item(&contract_and_token_id);
+        item.price = Some(price);
+
+        msg::reply(
+            MarketEvent::PriceUpdated {
+                nft_contract_id: *nft_contract_id,
+                token_id,
+                price,
+            },
+            0,
+        )
+        .unwrap();
+    }
+
+    /// Takes the item off the sale, the offers for it can still be accepted
+    /// Requirements:
+    /// * Only the item owner can cancel the listing
+    /// * The item must be on sale
+    /// Arguments:
+    /// * `nft_contract_id`: NFT contract address
+    /// * `token_id`: the token ID
+    pub fn cancel_listing(&mut self, nft_contract_id: &ActorId, token_id: U256) {
+        let contract_and_token_id = (*nft_contract_id, token_id);
+        let item = self.owned_item(&contract_and_token_id);
+        if item.price.is_none() {
+            panic!("The item is not on sale");
+        }
+        item.price = None;
+
+        msg::reply(
+            MarketEvent::ListingCancelled {
+                nft_contract_id: *nft_contract_id,
+                token_id,
+            },
+            0,
+        )
+        .unwrap();
+    }
+
+    /// Removes the item from the marketplace and revokes the marketplace approval
+    /// The offers are credited to the refunds of their bidders, see `withdraw_refunds`
+    /// The NFT held by the marketplace is transferred back to the item owner
+    /// Requirements:
+    /// * Only the item owner can delist it
+    /// * There must be no opened auction
+    /// Arguments:
+    /// * `nft_contract_id`: NFT contract address
+    /// * `token_id`: the token ID
+    pub async fn delist(&mut self, nft_contract_id: &ActorId, token_id: U256) {
+        let contract_and_token_id = (*nft_contract_id, token_id);
+        self.on_auction(&contract_and_token_id);
+        self.owned_item(&contract_and_token_id);
+        // the item is removed before waiting for the replies, so it can't be delisted twice
+        let item = self
+            .items
+            .remove(&contract_and_token_id)
+            .expect("Item does not exist");
+        for offer in &item.offers {
+            *self
+                .refunds
+                .entry((offer.id, offer.ft_contract_id))
+                .or_default() += offer.price;
+        }
+        if let Some(keys) = self.items_by_owner.get_mut(&item.owner_id) {
+            keys.remove(&contract_and_token_id);
+            if keys.is_empty() {
+                self.items_by_owner.remove(&item.owner_id);
+            }
+        }
+
+        if nft_owner_of(nft_contract_id, token_id).await == exec::program_id() {
+            nft_transfer(nft_contract_id, &item.owner_id, token_id).await;
+        } else {
+            nft_revoke_approval(nft_contract_id, token_id).await;
+        }
+
+        msg::reply(
+            MarketEvent::ItemDelisted {
+                nft_contract_id: *nft_contract_id,
+                token_id,
+            },
+            0,
+        )
+        .unwrap();
+    }
+
+    /// Returns the item checking that `msg::source()` is its owner
+    fn owned_item(&mut self, contract_and_token_id: &ContractAndTokenId) -> &mut Item {
+        let item = self
+            .items
+            .get_mut(contract_and_token_id)
+            .expect("Item does not exist");
+        if item.owner_id != msg::source() {
+            panic!("Only the item owner can make that action");
+        }
+        item
+    }
 }
@@ -34,6 +34,26 @@ fn start_auction(
     min_price: u128,
     bid_period: u64,
     duration: u64,
+) -> RunResult {
+    start_auction_with_prices(
+        market,
+        ft_contract_id,
+        min_price,
+        bid_period,
+        duration,
+        None,
+        None,
+    )
+}
+
+fn start_auction_with_prices(
+    market: &Program,
+    ft_contract_id: Option<ActorId>,
+    min_price: u128,
+    bid_period: u64,
+    duration: u64,
+    reserve_price: Option<u128>,
+    buy_now_price: Option<u128>,
 ) -> RunResult {
     market.send(
         USERS[0],
@@ -44,6 +64,18 @@ fn start_auction(
             min_price,
             bid_period,
             duration,
+            reserve_price,
+            buy_now_price,
+        },
+    )
+}
+
+fn settle(market: &Program) -> RunResult {
+    market.send(
+        USERS[0],
+        MarketAction::SettleAuction {
+            nft_contract_id: 2.into(),
+            token_id: 0.into(),
         },
     )
 }
@@ -97,6 +129,23 @@ fn create_auction_failures() {
     let res = start_auction(&market, None, 0, 60_000, 86_400_000);
     assert!(res.main_failed());
 
+    // must fail since the buy-now price is not greater than the min price
+    let res =
+        start_auction_with_prices(&market, None, 1_000, 60_000, 86_400_000, None, Some(1_000));
+    assert!(res.main_failed());
+
+    // must fail since the reserve price is greater than the buy-now price
+    let res = start_auction_with_prices(
+        &market,
+        None,
+        1_000,
+        60_000,
+        86_400_000,
+        Some(3_000),
+        Some(2_000),
+    );
+    assert!(res.main_failed());
+
     // start auction
     let res = start_auction(&market, None, 1_000, 60_000, 86_400_000);
     assert!(!res.main_failed());
@@ -305,8 +354,15 @@ fn auction_with_ft_token() {
 
     // Mints tokens for users
     USERS.iter().for_each(|user| {
-        let res = ft.send(*user, FTAction::Mint(100_000));
+        let res = ft.send(
+            USERS[0],
+            FTAction::Mint {
+                to: (*user).into(),
+                amount: 100_000,
+            },
+        );
         assert!(!res.main_failed());
+        approve_market(&ft, *user, 100_000);
     });
 
     // Users add bids
@@ -344,19 +400,44 @@ fn auction_with_ft_token() {
     let res = ft.send(USERS[0], FTAction::BalanceOf(TREASURY_ID.into()));
     assert!(res.contains(&(USERS[0], FTEvent::Balance(104).encode())));
 
-    // check the balance of seller
+    // check the balance of seller, whose own bid is still to withdraw
     let res = ft.send(USERS[0], FTAction::BalanceOf(USERS[0].into()));
     println!("{:?}", res.decoded_log::<FTEvent>());
-    assert!(res.contains(&(USERS[0], FTEvent::Balance(110_296).encode())));
+    assert!(res.contains(&(USERS[0], FTEvent::Balance(100_196).encode())));
 
     // check the balance of buyer
     let res = ft.send(USERS[0], FTAction::BalanceOf(USERS[3].into()));
     assert!(res.contains(&(USERS[0], FTEvent::Balance(89_600).encode())));
 
-    // check the balances of user who don't win auctions
+    // the users who don't win the auction withdraw their bids
+    let res = ft.send(USERS[0], FTAction::BalanceOf(USERS[1].into()));
+    assert!(res.contains(&(USERS[0], FTEvent::Balance(89_800).encode())));
+    let res = market.send(
+        USERS[1],
+        MarketAction::WithdrawRefunds {
+            ft_contract_id: Some(1.into()),
+        },
+    );
+    assert!(res.contains(&(
+        USERS[1],
+        MarketEvent::RefundsWithdrawn {
+            ft_contract_id: Some(1.into()),
+            amount: 10_200,
+        }
+        .encode()
+    )));
     let res = ft.send(USERS[0], FTAction::BalanceOf(USERS[1].into()));
     assert!(res.contains(&(USERS[0], FTEvent::Balance(100_000).encode())));
 
+    // must fail since the refunds are already withdrawn
+    let res = market.send(
+        USERS[1],
+        MarketAction::WithdrawRefunds {
+            ft_contract_id: Some(1.into()),
+        },
+    );
+    assert!(res.main_failed());
+
     // Checks NFT item on the marketplace
     let res = market.send(
         USERS[0],
@@ -379,3 +460,357 @@ fn auction_with_ft_token() {
         .encode()
     )));
 }
+
+#[test]
+fn competing_bids() {
+    let sys = System::new();
+    sys.init_logger();
+    before_each_test(&sys);
+    let ft = sys.get_program(1);
+    let market = sys.get_program(3);
+    let res = start_auction(&market, Some(1.into()), 10_000, 60_000, 86_400_000);
+    assert!(!res.main_failed());
+    let ft_bid = |user: u64, price: u128| {
+        market.send(
+            user,
+            MarketAction::AddBid {
+                nft_contract_id: 2.into(),
+                token_id: 0.into(),
+                price,
+            },
+        )
+    };
+
+    for user in &USERS[1..3] {
+        let res = ft.send(
+            USERS[0],
+            FTAction::Mint {
+                to: (*user).into(),
+                amount: 100_000,
+            },
+        );
+        assert!(!res.main_failed());
+        approve_market(&ft, *user, 100_000);
+    }
+
+    let res = ft_bid(USERS[1], 15_000);
+    assert!(!res.main_failed());
+    let res = ft_bid(USERS[2], 20_000);
+    assert!(!res.main_failed());
+    // must fail since the competing bid is higher, and nothing is taken from the bidder
+    let res = ft_bid(USERS[1], 18_000);
+    assert!(res.main_failed());
+    let res = ft.send(USERS[0], FTAction::BalanceOf(USERS[1].into()));
+    assert!(res.contains(&(USERS[0], FTEvent::Balance(85_000).encode())));
+
+    sys.spend_blocks(86_400_000);
+    let res = settle(&market);
+    assert!(res.contains(&(
+        USERS[0],
+        MarketEvent::AuctionSettled {
+            nft_contract_id: 2.into(),
+            token_id: 0.into(),
+            price: 20_000,
+        }
+        .encode()
+    )));
+
+    // the outbid bid is left to withdraw
+    let res = market.send(
+        USERS[1],
+        MarketAction::WithdrawRefunds {
+            ft_contract_id: Some(1.into()),
+        },
+    );
+    assert!(res.contains(&(
+        USERS[1],
+        MarketEvent::RefundsWithdrawn {
+            ft_contract_id: Some(1.into()),
+            amount: 15_000,
+        }
+        .encode()
+    )));
+    let res = ft.send(USERS[0], FTAction::BalanceOf(USERS[1].into()));
+    assert!(res.contains(&(USERS[0], FTEvent::Balance(100_000).encode())));
+}
+
+#[test]
+fn withdraw_refunds_when_transfer_fails() {
+    let sys = System::new();
+    sys.init_logger();
+    before_each_test(&sys);
+    let ft = sys.get_program(1);
+    let market = sys.get_program(3);
+    let res = start_auction(&market, Some(1.into()), 10_000, 60_000, 86_400_000);
+    assert!(!res.main_failed());
+
+    for user in &USERS[1..3] {
+        let res = ft.send(
+            USERS[0],
+            FTAction::Mint {
+                to: (*user).into(),
+                amount: 100_000,
+            },
+        );
+        assert!(!res.main_failed());
+        approve_market(&ft, *user, 100_000);
+    }
+    for (user, price) in [(USERS[1], 15_000), (USERS[2], 20_000)] {
+        let res = market.send(
+            user,
+            MarketAction::AddBid {
+                nft_contract_id: 2.into(),
+                token_id: 0.into(),
+                price,
+            },
+        );
+        assert!(!res.main_failed());
+    }
+    let withdraw = || {
+        market.send(
+            USERS[1],
+            MarketAction::WithdrawRefunds {
+                ft_contract_id: Some(1.into()),
+            },
+        )
+    };
+
+    // the paused token rejects the transfer, so the refunds are kept
+    let res = ft.send(USERS[0], FTAction::Pause);
+    assert!(!res.main_failed());
+    let res = withdraw();
+    assert!(res.contains(&(
+        USERS[1],
+        MarketEvent::RefundsWithdrawalFailed {
+            ft_contract_id: Some(1.into()),
+            amount: 15_000,
+        }
+        .encode()
+    )));
+
+    let res = ft.send(USERS[0], FTAction::Unpause);
+    assert!(!res.main_failed());
+    let res = withdraw();
+    assert!(res.contains(&(
+        USERS[1],
+        MarketEvent::RefundsWithdrawn {
+            ft_contract_id: Some(1.into()),
+            amount: 15_000,
+        }
+        .encode()
+    )));
+    let res = ft.send(USERS[0], FTAction::BalanceOf(USERS[1].into()));
+    assert!(res.contains(&(USERS[0], FTEvent::Balance(100_000).encode())));
+}
+
+#[test]
+fn settle_auction_when_payout_fails() {
+    let sys = System::new();
+    sys.init_logger();
+    before_each_test(&sys);
+    let ft = sys.get_program(1);
+    let market = sys.get_program(3);
+    let res = start_auction(&market, Some(1.into()), 10_000, 60_000, 86_400_000);
+    assert!(!res.main_failed());
+
+    let res = ft.send(
+        USERS[0],
+        FTAction::Mint {
+            to: USERS[1].into(),
+            amount: 100_000,
+        },
+    );
+    assert!(!res.main_failed());
+    approve_market(&ft, USERS[1], 100_000);
+    let res = market.send(
+        USERS[1],
+        MarketAction::AddBid {
+            nft_contract_id: 2.into(),
+            token_id: 0.into(),
+            price: 20_000,
+        },
+    );
+    assert!(!res.main_failed());
+
+    sys.spend_blocks(86_400_000);
+
+    // the paused token rejects the payouts, but the auction is settled
+    let res = ft.send(USERS[0], FTAction::Pause);
+    assert!(!res.main_failed());
+    let res = settle(&market);
+    assert!(res.contains(&(
+        USERS[0],
+        MarketEvent::AuctionSettled {
+            nft_contract_id: 2.into(),
+            token_id: 0.into(),
+            price: 20_000,
+        }
+        .encode()
+    )));
+    let nft = sys.get_program(2);
+    let res = nft.send(USERS[0], NFTAction::OwnerOf(0.into()));
+    assert!(res.contains(&(USERS[0], NFTEvent::OwnerOf(USERS[1].into()).encode())));
+
+    // the failed payouts are credited to the refunds of the treasury and the seller
+    let res = ft.send(USERS[0], FTAction::Unpause);
+    assert!(!res.main_failed());
+    for (user, amount) in [(TREASURY_ID, 200), (USERS[0], 19_800)] {
+        let res = market.send(
+            user,
+            MarketAction::WithdrawRefunds {
+                ft_contract_id: Some(1.into()),
+            },
+        );
+        assert!(res.contains(&(
+            user,
+            MarketEvent::RefundsWithdrawn {
+                ft_contract_id: Some(1.into()),
+                amount,
+            }
+            .encode()
+        )));
+        let res = ft.send(USERS[0], FTAction::BalanceOf(user.into()));
+        assert!(res.contains(&(USERS[0], FTEvent::Balance(amount).encode())));
+    }
+}
+
+#[test]
+fn reserve_price_is_not_met() {
+    let sys = System::new();
+    sys.init_logger();
+    before_each_test(&sys);
+    let market = sys.get_program(3);
+    let res = start_auction_with_prices(
+        &market,
+        None,
+        100_000,
+        60_000,
+        86_400_000,
+        Some(200_000),
+        None,
+    );
+    assert!(!res.main_failed());
+    let res = bid(&market, USERS[1], 150_000);
+    assert!(!res.main_failed());
+
+    sys.spend_blocks(86_400_001);
+
+    // the auction is cancelled since the highest bid is less than the reserve price
+    let res = settle(&market);
+    assert!(res.contains(&(
+        USERS[0],
+        MarketEvent::AuctionCancelled {
+            nft_contract_id: 2.into(),
+            token_id: 0.into(),
+        }
+        .encode()
+    )));
+    let nft = sys.get_program(2);
+    let res = nft.send(USERS[0], NFTAction::OwnerOf(0.into()));
+    assert!(res.contains(&(USERS[0], NFTEvent::OwnerOf(USERS[0].into()).encode())));
+}
+
+#[test]
+fn buy_now() {
+    let sys = System::new();
+    sys.init_logger();
+    before_each_test(&sys);
+    let market = sys.get_program(3);
+    let res = start_auction_with_prices(
+        &market,
+        None,
+        100_000,
+        60_000,
+        86_400_000,
+        None,
+        Some(500_000),
+    );
+    assert!(!res.main_failed());
+    let res = bid(&market, USERS[1], 150_000);
+    assert!(!res.main_failed());
+
+    // the bid reaching the buy-now price settles the auction
+    let res = bid(&market, USERS[2], 500_000);
+    assert!(res.contains(&(
+        USERS[2],
+        MarketEvent::AuctionSettled {
+            nft_contract_id: 2.into(),
+            token_id: 0.into(),
+            price: 500_000,
+        }
+        .encode()
+    )));
+    let nft = sys.get_program(2);
+    let res = nft.send(USERS[0], NFTAction::OwnerOf(0.into()));
+    assert!(res.contains(&(USERS[0], NFTEvent::OwnerOf(USERS[2].into()).encode())));
+}
+
+#[test]
+fn late_bid_extends_auction() {
+    let sys = System::new();
+    sys.init_logger();
+    before_each_test(&sys);
+    let market = sys.get_program(3);
+    let res = start_auction(&market, None, 100_000, 60_000, 86_400_000);
+    assert!(!res.main_failed());
+
+    sys.spend_blocks(86_390_000);
+    let res = bid(&market, USERS[1], 150_000);
+    assert!(!res.main_failed());
+
+    // must fail since the late bid extended the auction by the bid period
+    sys.spend_blocks(20_000);
+    let res = settle(&market);
+    assert!(res.main_failed());
+
+    sys.spend_blocks(60_000);
+    let res = settle(&market);
+    assert!(res.contains(&(
+        USERS[0],
+        MarketEvent::AuctionSettled {
+            nft_contract_id: 2.into(),
+            token_id: 0.into(),
+            price: 150_000,
+        }
+        .encode()
+    )));
+}
+
+#[test]
+fn cancel_auction() {
+    let sys = System::new();
+    sys.init_logger();
+    before_each_test(&sys);
+    let market = sys.get_program(3);
+    let cancel = || MarketAction::CancelAuction {
+        nft_contract_id: 2.into(),
+        token_id: 0.into(),
+    };
+    let res = start_auction(&market, None, 100_000, 60_000, 86_400_000);
+    assert!(!res.main_failed());
+
+    // must fail since only the item owner can cancel the auction
+    let res = market.send(USERS[1], cancel());
+    assert!(res.main_failed());
+
+    let res = market.send(USERS[0], cancel());
+    assert!(res.contains(&(
+        USERS[0],
+        MarketEvent::AuctionCancelled {
+            nft_contract_id: 2.into(),
+            token_id: 0.into(),
+        }
+        .encode()
+    )));
+    // must fail since the auction is cancelled
+    let res = bid(&market, USERS[1], 150_000);
+    assert!(res.main_failed());
+
+    let res = start_auction(&market, None, 100_000, 60_000, 86_400_000);
+    assert!(!res.main_failed());
+    let res = bid(&market, USERS[1], 150_000);
+    assert!(!res.main_failed());
+    // must fail since the auction already has a bid
+    let res = market.send(USERS[0], cancel());
+    assert!(res.main_failed());
+}
@@ -0,0 +1,56 @@
+use codec::Encode;
+use gtest::{Program, System};
+use nft_io::*;
+mod utils;
+use utils::*;
+
+fn mint(nft: &Program, media: &str, reference: &str) {
+    let res = nft.send(
+        USERS[0],
+        NFTAction::Mint {
+            media: media.to_string(),
+            reference: reference.to_string(),
+        },
+    );
+    assert!(!res.main_failed());
+}
+
+#[test]
+fn token_uri() {
+    let sys = System::new();
+    sys.init_logger();
+    let nft = Program::from_file(&sys, "../../target/wasm32-unknown-unknown/release/nft.wasm");
+    let res = nft.send(
+        USERS[0],
+        InitNFT {
+            name: String::from("MyToken"),
+            symbol: String::from("MTK"),
+            base_uri: "ipfs://".to_string(),
+            supply: 100.into(),
+            royalties: None,
+            history_retention: 0,
+            voucher_signer: None,
+            mint_config: MintConfig::default(),
+        },
+    );
+    assert!(res.log().is_empty());
+
+    // the empty media and reference fall back to the base URI
+    mint(&nft, "", "");
+    let res = nft.send(USERS[0], NFTAction::TokenURI(0.into()));
+    assert!(res.contains(&(
+        USERS[0],
+        NFTEvent::TokenURI(String::from("ipfs://0")).encode()
+    )));
+
+    mint(&nft, "media", "");
+    let res = nft.send(USERS[0], NFTAction::TokenURI(1.into()));
+    assert!(res.contains(&(USERS[0], NFTEvent::TokenURI(String::from("media")).encode())));
+
+    mint(&nft, "media", "reference");
+    let res = nft.send(USERS[0], NFTAction::TokenURI(2.into()));
+    assert!(res.contains(&(
+        USERS[0],
+        NFTEvent::TokenURI(String::from("reference")).encode()
+    )));
+}
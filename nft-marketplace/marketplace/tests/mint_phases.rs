@@ -0,0 +1,124 @@
+use codec::Encode;
+use ft_io::*;
+use gtest::{Program, RunResult, System};
+use nft_io::*;
+mod utils;
+use utils::*;
+
+fn mint(nft: &Program, user: u64) -> RunResult {
+    nft.send(
+        user,
+        NFTAction::Mint {
+            media: "".to_string(),
+            reference: "".to_string(),
+        },
+    )
+}
+
+fn set_phase(nft: &Program, phase: MintPhase) {
+    let res = nft.send(
+        USERS[0],
+        NFTAction::SetMintConfig(MintConfig {
+            ft_contract_id: Some(1.into()),
+            phases: vec![phase],
+        }),
+    );
+    assert!(res.contains(&(USERS[0], NFTEvent::MintConfigSet.encode())));
+}
+
+#[test]
+fn mint_phases() {
+    let sys = System::new();
+    init_ft(&sys);
+    init_nft(&sys);
+    let ft = sys.get_program(1);
+    let nft = sys.get_program(2);
+    let res = ft.send(
+        USERS[0],
+        FTAction::Mint {
+            to: USERS[1].into(),
+            amount: 1_000,
+        },
+    );
+    assert!(!res.main_failed());
+    let res = ft.send(
+        USERS[1],
+        FTAction::Approve {
+            to: 2.into(),
+            amount: 100,
+        },
+    );
+    assert!(!res.main_failed());
+
+    // must fail since only the contract owner can set the phases
+    let res = nft.send(USERS[1], NFTAction::SetMintConfig(MintConfig::default()));
+    assert!(res.main_failed());
+
+    // must fail since the only phase hasn't started yet
+    set_phase(
+        &nft,
+        MintPhase {
+            start: u64::MAX,
+            allowlist_only: false,
+            max_per_wallet: None,
+            price: 0,
+        },
+    );
+    assert!(mint(&nft, USERS[1]).main_failed());
+
+    set_phase(
+        &nft,
+        MintPhase {
+            start: 0,
+            allowlist_only: true,
+            max_per_wallet: Some(1),
+            price: 100,
+        },
+    );
+    // must fail since the account is not allowlisted
+    assert!(mint(&nft, USERS[1]).main_failed());
+
+    // must fail since only the contract owner can manage the allowlist
+    let res = nft.send(USERS[1], NFTAction::AddToAllowlist(vec![USERS[1].into()]));
+    assert!(res.main_failed());
+    let res = nft.send(USERS[0], NFTAction::AddToAllowlist(vec![USERS[1].into()]));
+    assert!(res.contains(&(
+        USERS[0],
+        NFTEvent::AddedToAllowlist(vec![USERS[1].into()]).encode()
+    )));
+
+    // must fail since the price is in the fungible token
+    let res = nft.send_with_value(
+        USERS[1],
+        NFTAction::Mint {
+            media: "".to_string(),
+            reference: "".to_string(),
+        },
+        100,
+    );
+    assert!(res.main_failed());
+
+    assert!(mint(&nft, USERS[1]).contains(&(
+        USERS[1],
+        NFTEvent::Transfer {
+            from: 0.into(),
+            to: USERS[1].into(),
+            token_id: 0.into(),
+        }
+        .encode()
+    )));
+    // must fail since the account reached its limit during the phase
+    assert!(mint(&nft, USERS[1]).main_failed());
+
+    set_phase(
+        &nft,
+        MintPhase {
+            start: 0,
+            allowlist_only: false,
+            max_per_wallet: None,
+            price: 100,
+        },
+    );
+    // the allowance was spent on the first token
+    assert!(mint(&nft, USERS[1]).contains(&(USERS[1], NFTEvent::MintPaymentFailed.encode())));
+}
@@ -100,8 +100,15 @@ fn add_offer_with_tokens() {
     add_market_data(&market, None, USERS[0], 0, Some(100_000));
 
     let ft = sys.get_program(1);
-    let res = ft.send(USERS[1], FTAction::Mint(100_000));
+    let res = ft.send(
+        USERS[0],
+        FTAction::Mint {
+            to: USERS[1].into(),
+            amount: 100_000,
+        },
+    );
     assert!(!res.main_failed());
+    approve_market(&ft, USERS[1], 100_000);
     offer(&market, USERS[1], Some(1.into()), 10_000);
 
     // check the market balance
@@ -143,8 +150,15 @@ fn add_offer_failures() {
 
     // add offer
     let ft = sys.get_program(1);
-    let res = ft.send(USERS[1], FTAction::Mint(100_000));
+    let res = ft.send(
+        USERS[0],
+        FTAction::Mint {
+            to: USERS[1].into(),
+            amount: 100_000,
+        },
+    );
     assert!(!res.main_failed());
+    approve_market(&ft, USERS[1], 100_000);
     let res = market.send(
         USERS[1],
         MarketAction::AddOffer {
@@ -190,8 +204,15 @@ fn accept_offer() {
 
     let ft = sys.get_program(1);
     let market = sys.get_program(3);
-    let res = ft.send(USERS[2], FTAction::Mint(100_000));
+    let res = ft.send(
+        USERS[0],
+        FTAction::Mint {
+            to: USERS[2].into(),
+            amount: 100_000,
+        },
+    );
     assert!(!res.main_failed());
+    approve_market(&ft, USERS[2], 100_000);
     add_market_data(&market, None, USERS[0], 0, Some(100_000));
     offer(&market, USERS[1], None, 100_000);
     offer(&market, USERS[2], Some(1.into()), 1_000);
@@ -297,8 +318,15 @@ fn accept_offer_failures() {
 
     let ft = sys.get_program(1);
     let market = sys.get_program(3);
-    let res = ft.send(USERS[2], FTAction::Mint(100_000));
+    let res = ft.send(
+        USERS[0],
+        FTAction::Mint {
+            to: USERS[2].into(),
+            amount: 100_000,
+        },
+    );
     assert!(!res.main_failed());
+    approve_market(&ft, USERS[2], 100_000);
     add_market_data(&market, None, USERS[0], 0, Some(100_000));
     offer(&market, USERS[1], None, 100_000);
     // must fail since only owner can accept offer
@@ -332,8 +360,15 @@ fn withdraw() {
 
     let ft = sys.get_program(1);
     let market = sys.get_program(3);
-    let res = ft.send(USERS[2], FTAction::Mint(100_000));
+    let res = ft.send(
+        USERS[0],
+        FTAction::Mint {
+            to: USERS[2].into(),
+            amount: 100_000,
+        },
+    );
     assert!(!res.main_failed());
+    approve_market(&ft, USERS[2], 100_000);
     add_market_data(&market, None, USERS[0], 0, Some(100_000));
     offer(&market, USERS[1], None, 100_000);
     offer(&market, USERS[2], Some(1.into()), 1_000);
@@ -435,8 +470,15 @@ fn withdraws_failure() {
 
     let ft = sys.get_program(1);
     let market = sys.get_program(3);
-    let res = ft.send(USERS[2], FTAction::Mint(100_000));
+    let res = ft.send(
+        USERS[0],
+        FTAction::Mint {
+            to: USERS[2].into(),
+            amount: 100_000,
+        },
+    );
     assert!(!res.main_failed());
+    approve_market(&ft, USERS[2], 100_000);
     add_market_data(&market, None, USERS[0], 0, Some(100_000));
     offer(&market, USERS[1], None, 100_000);
     offer(&market, USERS[2], Some(1.into()), 1_000);
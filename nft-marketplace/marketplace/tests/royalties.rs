@@ -0,0 +1,226 @@
+use codec::Encode;
+use gstd::BTreeMap;
+use gtest::{Program, System};
+use nft_io::*;
+mod utils;
+use utils::*;
+
+fn init_with_mint(sys: &System, royalties: Option<Royalties>) -> Program {
+    sys.init_logger();
+    init_ft(&sys);
+    let nft = Program::from_file(&sys, "../../target/wasm32-unknown-unknown/release/nft.wasm");
+    let res = nft.send(
+        USERS[0],
+        InitNFT {
+            name: String::from("MyToken"),
+            symbol: String::from("MTK"),
+            base_uri: "".to_string(),
+            supply: 100.into(),
+            royalties,
+            history_retention: 0,
+            voucher_signer: None,
+            mint_config: MintConfig::default(),
+        },
+    );
+    assert!(res.log().is_empty());
+    for _ in 0..2 {
+        let res = nft.send(
+            USERS[0],
+            NFTAction::Mint {
+                media: "".to_string(),
+                reference: "".to_string(),
+            },
+        );
+        assert!(!res.main_failed());
+    }
+    nft
+}
+
+#[test]
+fn token_royalties() {
+    let sys = System::new();
+    let nft = init_with_mint(&sys, None);
+    let royalties = Royalties {
+        accounts: BTreeMap::from([(USERS[3].into(), 10_000)]),
+        percent: 1_000,
+    };
+
+    // must fail since only the contract owner can set royalties
+    let res = nft.send(
+        USERS[1],
+        NFTAction::SetTokenRoyalties {
+            token_id: 1.into(),
+            royalties: Some(royalties.clone()),
+        },
+    );
+    assert!(res.main_failed());
+    // must fail since the royalty is more than 100%
+    let res = nft.send(
+        USERS[0],
+        NFTAction::SetTokenRoyalties {
+            token_id: 1.into(),
+            royalties: Some(Royalties {
+                percent: 10_001,
+                ..royalties.clone()
+            }),
+        },
+    );
+    assert!(res.main_failed());
+
+    let res = nft.send(
+        USERS[0],
+        NFTAction::SetTokenRoyalties {
+            token_id: 1.into(),
+            royalties: Some(royalties),
+        },
+    );
+    assert!(res.contains(&(
+        USERS[0],
+        NFTEvent::RoyaltiesSet { token_id: 1.into() }.encode()
+    )));
+
+    let res = nft.send(
+        USERS[0],
+        NFTAction::RoyaltyInfo {
+            token_id: 1.into(),
+            sale_price: 100_000,
+        },
+    );
+    assert!(res.contains(&(
+        USERS[0],
+        NFTEvent::RoyaltyInfo(BTreeMap::from([(USERS[3].into(), 10_000)])).encode()
+    )));
+    let res = nft.send(
+        USERS[0],
+        NFTAction::NFTPayout {
+            owner: USERS[0].into(),
+            token_id: 1.into(),
+            amount: 100_000,
+        },
+    );
+    assert!(res.contains(&(
+        USERS[0],
+        NFTEvent::NFTPayout(BTreeMap::from([
+            (USERS[0].into(), 90_000),
+            (USERS[3].into(), 10_000)
+        ]))
+        .encode()
+    )));
+
+    // the token without royalties pays everything to the owner
+    let res = nft.send(
+        USERS[0],
+        NFTAction::RoyaltyInfo {
+            token_id: 0.into(),
+            sale_price: 100_000,
+        },
+    );
+    assert!(res.contains(&(USERS[0], NFTEvent::RoyaltyInfo(BTreeMap::new()).encode())));
+    let res = nft.send(
+        USERS[0],
+        NFTAction::NFTPayout {
+            owner: USERS[0].into(),
+            token_id: 0.into(),
+            amount: 100_000,
+        },
+    );
+    assert!(res.contains(&(
+        USERS[0],
+        NFTEvent::NFTPayout(BTreeMap::from([(USERS[0].into(), 100_000)])).encode()
+    )));
+}
+
+fn check_payouts(nft: &Program, token_id: u64, royalty_info: Payout, payouts: Payout) {
+    let res = nft.send(
+        USERS[0],
+        NFTAction::RoyaltyInfo {
+            token_id: token_id.into(),
+            sale_price: 100_000,
+        },
+    );
+    assert!(res.contains(&(USERS[0], NFTEvent::RoyaltyInfo(royalty_info).encode())));
+    let res = nft.send(
+        USERS[0],
+        NFTAction::NFTPayout {
+            owner: USERS[0].into(),
+            token_id: token_id.into(),
+            amount: 100_000,
+        },
+    );
+    assert!(res.contains(&(USERS[0], NFTEvent::NFTPayout(payouts).encode())));
+}
+
+#[test]
+fn collection_royalties() {
+    let sys = System::new();
+    let nft = init_with_mint(
+        &sys,
+        Some(Royalties {
+            accounts: BTreeMap::from([(USERS[2].into(), 10_000)]),
+            percent: 500,
+        }),
+    );
+    let check_collection_payouts = |token_id| {
+        check_payouts(
+            &nft,
+            token_id,
+            BTreeMap::from([(USERS[2].into(), 5_000)]),
+            BTreeMap::from([(USERS[0].into(), 95_000), (USERS[2].into(), 5_000)]),
+        )
+    };
+
+    // the tokens without their own royalties use the collection royalties
+    check_collection_payouts(0);
+
+    let res = nft.send(
+        USERS[0],
+        NFTAction::SetTokenRoyalties {
+            token_id: 1.into(),
+            royalties: Some(Royalties {
+                accounts: BTreeMap::from([(USERS[3].into(), 10_000)]),
+                percent: 1_000,
+            }),
+        },
+    );
+    assert!(!res.main_failed());
+    check_payouts(
+        &nft,
+        1,
+        BTreeMap::from([(USERS[3].into(), 10_000)]),
+        BTreeMap::from([(USERS[0].into(), 90_000), (USERS[3].into(), 10_000)]),
+    );
+    // the override doesn't change the other tokens
+    check_collection_payouts(0);
+
+    // the token falls back to the collection royalties when its override is removed
+    let res = nft.send(
+        USERS[0],
+        NFTAction::SetTokenRoyalties {
+            token_id: 1.into(),
+            royalties: None,
+        },
+    );
+    assert!(!res.main_failed());
+    check_collection_payouts(1);
+}
+
+#[test]
+fn undistributed_royalty() {
+    let sys = System::new();
+    // the accounts share only half of the royalty
+    let nft = init_with_mint(
+        &sys,
+        Some(Royalties {
+            accounts: BTreeMap::from([(USERS[2].into(), 5_000)]),
+            percent: 1_000,
+        }),
+    );
+
+    // the owner receives the sale price less the whole royalty, not only the paid out part
+    check_payouts(
+        &nft,
+        0,
+        BTreeMap::from([(USERS[2].into(), 5_000)]),
+        BTreeMap::from([(USERS[0].into(), 90_000), (USERS[2].into(), 5_000)]),
+    );
+}
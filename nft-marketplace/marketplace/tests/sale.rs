@@ -88,8 +88,15 @@ fn buy_with_tokens() {
     before_each_test(&sys);
     let ft = sys.get_program(1);
     // mint ft
-    let res = ft.send(USERS[1], FTAction::Mint(10_000));
+    let res = ft.send(
+        USERS[0],
+        FTAction::Mint {
+            to: USERS[1].into(),
+            amount: 10_000,
+        },
+    );
     assert!(!res.main_failed());
+    approve_market(&ft, USERS[1], 10_000);
     let nft = sys.get_program(2);
     // mint nft
     let res = nft.send(
@@ -201,3 +208,246 @@ fn buy_failures() {
     );
     assert!(res.main_failed());
 }
+
+#[test]
+fn update_cancel_and_delist() {
+    let sys = System::new();
+    sys.init_logger();
+    before_each_test(&sys);
+    let nft = sys.get_program(2);
+    let res = nft.send(
+        USERS[0],
+        NFTAction::Mint {
+            media: "".to_string(),
+            reference: "".to_string(),
+        },
+    );
+    assert!(!res.main_failed());
+    let market = sys.get_program(3);
+    add_market_data(&market, None, USERS[0], 0, Some(100_000));
+
+    let update_price = || MarketAction::UpdatePrice {
+        nft_contract_id: 2.into(),
+        token_id: 0.into(),
+        price: 1_000,
+    };
+    // must fail since only the item owner can update the price
+    let res = market.send(USERS[1], update_price());
+    assert!(res.main_failed());
+    let res = market.send(USERS[0], update_price());
+    assert!(res.contains(&(
+        USERS[0],
+        MarketEvent::PriceUpdated {
+            nft_contract_id: 2.into(),
+            token_id: 0.into(),
+            price: 1_000,
+        }
+        .encode()
+    )));
+
+    let res = market.send_with_value(
+        USERS[1],
+        MarketAction::AddOffer {
+            nft_contract_id: 2.into(),
+            ft_contract_id: None,
+            token_id: 0.into(),
+            price: 500,
+        },
+        500,
+    );
+    assert!(!res.main_failed());
+
+    let cancel_listing = || MarketAction::CancelListing {
+        nft_contract_id: 2.into(),
+        token_id: 0.into(),
+    };
+    let res = market.send(USERS[0], cancel_listing());
+    assert!(res.contains(&(
+        USERS[0],
+        MarketEvent::ListingCancelled {
+            nft_contract_id: 2.into(),
+            token_id: 0.into(),
+        }
+        .encode()
+    )));
+    // must fail since the item is not on sale anymore
+    let res = market.send(USERS[0], cancel_listing());
+    assert!(res.main_failed());
+    let res = market.send_with_value(
+        USERS[1],
+        MarketAction::BuyItem {
+            nft_contract_id: 2.into(),
+            token_id: 0.into(),
+        },
+        1_000,
+    );
+    assert!(res.main_failed());
+    // the offer is kept after the listing is cancelled
+    let res = market.send(
+        USERS[0],
+        MarketAction::Item {
+            nft_contract_id: 2.into(),
+            token_id: 0.into(),
+        },
+    );
+    assert!(res.contains(&(
+        USERS[0],
+        MarketEvent::ItemInfo(Item {
+            owner_id: USERS[0].into(),
+            nft_contract_id: 2.into(),
+            ft_contract_id: None,
+            token_id: 0.into(),
+            price: None,
+            auction: None,
+            offers: vec![Offer {
+                hash: get_hash(&2.into(), None, 500),
+                id: USERS[1].into(),
+                ft_contract_id: None,
+                price: 500,
+            }],
+        })
+        .encode()
+    )));
+
+    let delist = || MarketAction::Delist {
+        nft_contract_id: 2.into(),
+        token_id: 0.into(),
+    };
+    // must fail since only the item owner can delist it
+    let res = market.send(USERS[1], delist());
+    assert!(res.main_failed());
+    let res = market.send(USERS[0], delist());
+    assert!(res.contains(&(
+        USERS[0],
+        MarketEvent::ItemDelisted {
+            nft_contract_id: 2.into(),
+            token_id: 0.into(),
+        }
+        .encode()
+    )));
+    let res = market.send(
+        USERS[0],
+        MarketAction::Item {
+            nft_contract_id: 2.into(),
+            token_id: 0.into(),
+        },
+    );
+    assert!(res.contains(&(USERS[0], MarketEvent::ItemInfo(Item::default()).encode())));
+    // must fail since the item is delisted
+    let res = market.send_with_value(
+        USERS[1],
+        MarketAction::BuyItem {
+            nft_contract_id: 2.into(),
+            token_id: 0.into(),
+        },
+        1_000,
+    );
+    assert!(res.main_failed());
+
+    // the offer is left to withdraw
+    let res = market.send(
+        USERS[1],
+        MarketAction::WithdrawRefunds {
+            ft_contract_id: None,
+        },
+    );
+    assert!(res.contains(&(
+        USERS[1],
+        MarketEvent::RefundsWithdrawn {
+            ft_contract_id: None,
+            amount: 500,
+        }
+        .encode()
+    )));
+}
+
+#[test]
+fn safe_transfer_to_market() {
+    let sys = System::new();
+    sys.init_logger();
+    before_each_test(&sys);
+    let nft = sys.get_program(2);
+    let market = sys.get_program(3);
+    for token_id in 0..2u64 {
+        let res = nft.send(
+            USERS[0],
+            NFTAction::Mint {
+                media: "".to_string(),
+                reference: "".to_string(),
+            },
+        );
+        assert!(!res.main_failed());
+        let res = nft.send(
+            USERS[0],
+            NFTAction::SafeTransfer {
+                to: MARKET_ID.into(),
+                token_id: token_id.into(),
+                data: vec![],
+            },
+        );
+        assert!(res.contains(&(
+            USERS[0],
+            NFTEvent::Transfer {
+                from: USERS[0].into(),
+                to: MARKET_ID.into(),
+                token_id: token_id.into(),
+            }
+            .encode()
+        )));
+    }
+
+    // the received NFT is the item of its previous owner
+    let res = market.send(
+        USERS[0],
+        MarketAction::Item {
+            nft_contract_id: 2.into(),
+            token_id: 0.into(),
+        },
+    );
+    assert!(res.contains(&(
+        USERS[0],
+        MarketEvent::ItemInfo(Item {
+            owner_id: USERS[0].into(),
+            nft_contract_id: 2.into(),
+            ft_contract_id: None,
+            token_id: 0.into(),
+            price: None,
+            auction: None,
+            offers: vec![],
+        })
+        .encode()
+    )));
+
+    let res = market.send(
+        USERS[0],
+        MarketAction::UpdatePrice {
+            nft_contract_id: 2.into(),
+            token_id: 0.into(),
+            price: 1_000,
+        },
+    );
+    assert!(!res.main_failed());
+    let res = market.send_with_value(
+        USERS[1],
+        MarketAction::BuyItem {
+            nft_contract_id: 2.into(),
+            token_id: 0.into(),
+        },
+        1_000,
+    );
+    assert!(!res.main_failed());
+    let res = nft.send(USERS[0], NFTAction::OwnerOf(0.into()));
+    assert!(res.contains(&(USERS[0], NFTEvent::OwnerOf(USERS[1].into()).encode())));
+
+    // the delisted NFT is given back to its owner
+    let res = market.send(
+        USERS[0],
+        MarketAction::Delist {
+            nft_contract_id: 2.into(),
+            token_id: 1.into(),
+        },
+    );
+    assert!(!res.main_failed());
+    let res = nft.send(USERS[0], NFTAction::OwnerOf(1.into()));
+    assert!(res.contains(&(USERS[0], NFTEvent::OwnerOf(USERS[0].into()).encode())));
+}
@@ -8,6 +8,7 @@ use primitive_types::H256;
 
 pub const USERS: &'static [u64] = &[4, 5, 6, 7];
 pub const TREASURY_ID: u64 = 8;
+pub const MARKET_ID: u64 = 3;
 
 pub fn init_ft(sys: &System) {
     let ft = Program::from_file(
@@ -20,6 +21,10 @@ pub fn init_ft(sys: &System) {
         InitConfig {
             name: String::from("MyToken"),
             symbol: String::from("MTK"),
+            decimals: 18,
+            admin: USERS[0].into(),
+            minters: vec![USERS[0].into()],
+            max_supply: None,
         },
     );
 
@@ -38,6 +43,9 @@ pub fn init_nft(sys: &System) {
             base_uri: "".to_string(),
             supply: 100.into(),
             royalties: None,
+            history_retention: 0,
+            voucher_signer: None,
+            mint_config: MintConfig::default(),
         },
     );
     assert!(res.log().is_empty());
@@ -57,6 +65,17 @@ pub fn init_market(sys: &System) {
     assert!(res.log().is_empty());
 }
 
+pub fn approve_market(ft: &Program, user: u64, amount: u128) {
+    let res = ft.send(
+        user,
+        FTAction::Approve {
+            to: MARKET_ID.into(),
+            amount,
+        },
+    );
+    assert!(!res.main_failed());
+}
+
 pub fn add_market_data(
     market: &Program,
     ft_contract_id: Option<ActorId>,
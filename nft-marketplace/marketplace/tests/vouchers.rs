@@ -0,0 +1,99 @@
+use codec::Encode;
+use gstd::ActorId;
+use gtest::{Program, System};
+use nft_io::*;
+use schnorrkel::{ExpansionMode, Keypair, MiniSecretKey};
+mod utils;
+use utils::*;
+
+fn init_nft_with_signer(sys: &System, signer: ActorId) -> Program {
+    sys.init_logger();
+    let nft = Program::from_file(&sys, "../../target/wasm32-unknown-unknown/release/nft.wasm");
+
+    let res = nft.send(
+        USERS[0],
+        InitNFT {
+            name: String::from("MyToken"),
+            symbol: String::from("MTK"),
+            base_uri: "".to_string(),
+            supply: 2.into(),
+            royalties: None,
+            history_retention: 0,
+            voucher_signer: Some(signer),
+            mint_config: MintConfig::default(),
+        },
+    );
+    assert!(res.log().is_empty());
+    nft
+}
+
+fn sign_voucher(keypair: &Keypair, payload: VoucherPayload) -> [u8; 64] {
+    keypair
+        .sign_simple(b"substrate", &payload.encode())
+        .to_bytes()
+}
+
+#[test]
+fn redeem_voucher() {
+    let sys = System::new();
+    let keypair = MiniSecretKey::from_bytes(&[7; 32])
+        .unwrap()
+        .expand_to_keypair(ExpansionMode::Ed25519);
+    let nft = init_nft_with_signer(&sys, ActorId::new(keypair.public.to_bytes()));
+    let payload = |min_price| VoucherPayload {
+        nft: 1.into(),
+        token_id: 5.into(),
+        media: "media".to_string(),
+        reference: "reference".to_string(),
+        min_price,
+    };
+    let redeem = |min_price, signature| NFTAction::RedeemVoucher {
+        token_id: 5.into(),
+        media: "media".to_string(),
+        reference: "reference".to_string(),
+        min_price,
+        signature,
+    };
+    let signature = sign_voucher(&keypair, payload(1_000));
+
+    // must fail since the value is less than the minimum price
+    let res = nft.send_with_value(USERS[1], redeem(1_000, signature), 999);
+    assert!(res.main_failed());
+
+    // must fail since the voucher was signed for another price
+    let res = nft.send_with_value(USERS[1], redeem(100, signature), 1_000);
+    assert!(res.main_failed());
+
+    let res = nft.send_with_value(USERS[1], redeem(1_000, signature), 1_000);
+    assert!(res.contains(&(
+        USERS[1],
+        NFTEvent::Transfer {
+            from: 0.into(),
+            to: USERS[1].into(),
+            token_id: 5.into(),
+        }
+        .encode()
+    )));
+
+    // must fail since the voucher is already redeemed
+    let res = nft.send_with_value(USERS[2], redeem(1_000, signature), 1_000);
+    assert!(res.main_failed());
+
+    // the redeemed voucher counts towards the supply
+    let res = nft.send(
+        USERS[0],
+        NFTAction::Mint {
+            media: "".to_string(),
+            reference: "".to_string(),
+        },
+    );
+    assert!(!res.main_failed());
+    let res = nft.send(
+        USERS[0],
+        NFTAction::Mint {
+            media: "".to_string(),
+            reference: "".to_string(),
+        },
+    );
+    assert!(res.main_failed());
+}
@@ -1,8 +1,10 @@
 #![no_std]
 use codec::{Decode, Encode};
 use gstd::{prelude::*, ActorId, BTreeMap, String, Vec};
+pub use non_fungible_token::attributes::AttributeValue;
+pub use non_fungible_token::royalties::{Payout, Royalties};
+pub use non_fungible_token::token::TokenMetadata;
 use primitive_types::U256;
-pub use royalties::*;
 use scale_info::TypeInfo;
 
 #[derive(Debug, Encode, Decode, TypeInfo)]
@@ -12,20 +14,141 @@ pub struct InitNFT {
     pub base_uri: String,
     pub supply: U256,
     pub royalties: Option<Royalties>,
+    /// The number of the latest history records kept per token and per account,
+    /// zero disables the history.
+    pub history_retention: u32,
+    /// The creator signing the vouchers for lazy minting and receiving their payments,
+    /// `None` disables the vouchers.
+    pub voucher_signer: Option<ActorId>,
+    pub mint_config: MintConfig,
+}
+
+/// The sale phases of `NFTAction::Mint`, without phases anyone can mint for free.
+#[derive(Debug, Default, Clone, Encode, Decode, TypeInfo)]
+pub struct MintConfig {
+    /// The fungible token the mint price is paid in, `None` for the native value
+    pub ft_contract_id: Option<ActorId>,
+    /// The phases sorted by their start, the latest started one is the current phase
+    pub phases: Vec<MintPhase>,
+}
+
+#[derive(Debug, Clone, Encode, Decode, TypeInfo)]
+pub struct MintPhase {
+    /// The block timestamp the phase starts at
+    pub start: u64,
+    /// Only the allowlisted accounts can mint during the phase
+    pub allowlist_only: bool,
+    /// The number of tokens an account can mint during the phase, `None` for no limit
+    pub max_per_wallet: Option<u32>,
+    /// The price of a token paid to the contract owner
+    pub price: u128,
 }
 
 #[derive(Debug, Encode, Decode, TypeInfo)]
 pub enum NFTAction {
-    Mint { media: String, reference: String },
+    Mint {
+        media: String,
+        reference: String,
+    },
     Burn(U256),
-    Transfer { to: ActorId, token_id: U256 },
-    Approve { to: ActorId, token_id: U256 },
+    Transfer {
+        to: ActorId,
+        token_id: U256,
+    },
+    Approve {
+        to: ActorId,
+        token_id: U256,
+    },
+    /// Removes the approval of the token, only the owner or the approved actor can revoke it.
+    RevokeApproval(U256),
     OwnerOf(U256),
     BalanceOf(ActorId),
     TokensForOwner(ActorId),
-    NFTPayout { owner: ActorId, amount: u128 },
+    NFTPayout {
+        owner: ActorId,
+        token_id: U256,
+        amount: u128,
+    },
+    ApproveForAll {
+        to: ActorId,
+        approved: bool,
+    },
+    IsApprovedForAll {
+        owner: ActorId,
+        operator: ActorId,
+    },
+    TotalSupply,
+    TokenByIndex(U256),
+    SafeTransfer {
+        to: ActorId,
+        token_id: U256,
+        data: Vec<u8>,
+    },
+    TokenURI(U256),
+    /// Replaces the token metadata, if `freeze` is true it can't be changed anymore.
+    /// Only the contract owner can set metadata.
+    SetTokenMetadata {
+        token_id: U256,
+        metadata: TokenMetadata,
+        freeze: bool,
+    },
+    RoyaltyInfo {
+        token_id: U256,
+        sale_price: u128,
+    },
+    /// Overrides the collection royalties for the token, `None` removes the override.
+    /// Only the contract owner can set royalties.
+    SetTokenRoyalties {
+        token_id: U256,
+        royalties: Option<Royalties>,
+    },
+    /// Mints the token described by the voucher to the buyer and forwards the attached value to the creator.
+    /// `signature` is the sr25519 signature of the voucher signer over the SCALE-encoded [`VoucherPayload`].
+    /// The attached value must be at least `min_price`, and each voucher can be redeemed once.
+    RedeemVoucher {
+        token_id: U256,
+        media: String,
+        reference: String,
+        min_price: u128,
+        signature: [u8; 64],
+    },
+    /// Replaces the sale phases and resets the numbers of tokens minted by accounts.
+    /// Only the contract owner can set them.
+    SetMintConfig(MintConfig),
+    /// Only the contract owner can manage the allowlist.
+    AddToAllowlist(Vec<ActorId>),
+    RemoveFromAllowlist(Vec<ActorId>),
+    /// Only the attribute updaters can set attributes.
+    SetAttribute {
+        token_id: U256,
+        key: String,
+        value: AttributeValue,
+    },
+    /// Only the attribute updaters can remove attributes.
+    RemoveAttribute {
+        token_id: U256,
+        key: String,
+    },
+    /// Allows or disallows the account to change the token attributes.
+    /// Only the contract owner can manage the attribute updaters.
+    SetAttributeUpdater {
+        account: ActorId,
+        allowed: bool,
+    },
+}
+
+/// The message signed by the creator to allow minting the token on its first purchase.
+#[derive(Debug, Encode, Decode, TypeInfo)]
+pub struct VoucherPayload {
+    /// The NFT contract the voucher is redeemed in
+    pub nft: ActorId,
+    pub token_id: U256,
+    pub media: String,
+    pub reference: String,
+    pub min_price: u128,
 }
 
+/// The first variants mirror `non_fungible_token::Event`, which the library replies with.
 #[derive(Debug, Encode, Decode, TypeInfo)]
 pub enum NFTEvent {
     Transfer {
@@ -45,6 +168,41 @@ pub enum NFTEvent {
     },
     OwnerOf(ActorId),
     BalanceOf(U256),
+    IsApprovedForAll(bool),
     TokensForOwner(Vec<U256>),
+    TotalSupply(U256),
+    TokenByIndex(U256),
+    TransferRejected {
+        from: ActorId,
+        to: ActorId,
+        token_id: U256,
+    },
+    TokenURI(String),
+    TokenMetadataSet {
+        token_id: U256,
+        frozen: bool,
+    },
+    RoyaltyInfo(Payout),
+    RoyaltiesSet {
+        token_id: U256,
+    },
+    AttributeSet {
+        token_id: U256,
+        key: String,
+        value: AttributeValue,
+    },
+    AttributeRemoved {
+        token_id: U256,
+        key: String,
+    },
     NFTPayout(BTreeMap<ActorId, u128>),
+    MintConfigSet,
+    AddedToAllowlist(Vec<ActorId>),
+    RemovedFromAllowlist(Vec<ActorId>),
+    /// The mint price wasn't paid in the fungible token, so nothing was minted
+    MintPaymentFailed,
+    AttributeUpdaterSet {
+        account: ActorId,
+        allowed: bool,
+    },
 }
@@ -1,117 +1,201 @@
 #![no_std]
 
+use codec::Encode;
+use ft_io::{FTAction, FTEvent};
 use gstd::{exec, msg, prelude::*, ActorId};
-use primitive_types::U256;
+use primitive_types::{H256, U256};
+use schnorrkel::{PublicKey, Signature};
 
 pub use nft_io::*;
-pub use royalties::*;
 
 pub mod state;
-pub use state::{State, StateReply};
+pub use state::{State, StateReply, TokenAndUser};
 
+use non_fungible_token::attributes::NonFungibleTokenAttributes;
 use non_fungible_token::base::NonFungibleTokenBase;
+use non_fungible_token::enumerable::NonFungibleTokenEnumerable;
+use non_fungible_token::metadata::NonFungibleTokenMetadata;
+use non_fungible_token::royalties::NonFungibleTokenRoyalties;
 use non_fungible_token::token::TokenMetadata;
 use non_fungible_token::NonFungibleToken;
 
 const ZERO_ID: ActorId = ActorId::new([0u8; 32]);
+/// Signing context used by Substrate wallets for sr25519 signatures.
+const SIGNING_CONTEXT: &[u8] = b"substrate";
 
 #[derive(Debug, Default)]
 pub struct NFT {
     pub tokens: NonFungibleToken,
     pub owner: ActorId,
-    pub owner_to_ids: BTreeMap<ActorId, Vec<U256>>,
     pub supply: U256,
-    pub royalties: Option<Royalties>,
     pub token_id: U256,
+    /// The number of the minted tokens including the redeemed vouchers
+    pub minted: U256,
+    pub voucher_signer: Option<ActorId>,
+    pub redeemed_vouchers: BTreeSet<U256>,
+    pub mint_config: MintConfig,
+    pub allowlist: BTreeSet<ActorId>,
+    pub minted_in_phase: BTreeMap<(u32, ActorId), u32>,
 }
 
 static mut CONTRACT: Option<NFT> = None;
 
 impl NFT {
-    fn mint(&mut self, media: String, reference: String) {
-        if self.token_id >= self.supply {
-            panic!("No tokens left");
+    async fn mint(&mut self, media: String, reference: String) {
+        let minter = msg::source();
+        let phase = self.check_phase(&minter);
+        self.check_supply();
+
+        if let Some((phase, price)) = phase {
+            if !self.pay_mint_price(&minter, price).await {
+                // the state saved while waiting for the payment isn't reverted by panicking
+                self.minted = self.minted.saturating_sub(U256::one());
+                if let Some(minted) = self.minted_in_phase.get_mut(&(phase, minter)) {
+                    *minted = minted.saturating_sub(1);
+                }
+                msg::reply(NFTEvent::MintPaymentFailed, 0).unwrap();
+                return;
+            }
+        }
+
+        // skip the IDs taken by the redeemed vouchers
+        while self.redeemed_vouchers.contains(&self.token_id) {
+            self.token_id = self.token_id.saturating_add(U256::one());
         }
         let token_id = self.token_id;
         self.token_id = self.token_id.saturating_add(U256::one());
+        self.tokens
+            .mint_to(&minter, token_id, Some(metadata(media, reference)));
+    }
+
+    /// Returns the index and the price of the current phase, if any,
+    /// after counting the token minted by `minter` during it
+    fn check_phase(&mut self, minter: &ActorId) -> Option<(u32, u128)> {
+        if self.mint_config.phases.is_empty() {
+            return None;
+        }
+        let (index, phase) = self.current_phase().expect("Minting hasn't started yet");
+        let (allowlist_only, max_per_wallet, price) =
+            (phase.allowlist_only, phase.max_per_wallet, phase.price);
 
-        self.tokens.owner_by_id.insert(token_id, msg::source());
-        self.owner_to_ids
-            .entry(msg::source())
-            .and_modify(|ids| ids.push(token_id))
-            .or_insert_with(|| vec![token_id]);
-        let metadata = TokenMetadata {
-            title: None,
-            description: None,
-            media: Some(media),
-            reference: Some(reference),
+        if allowlist_only && !self.allowlist.contains(minter) {
+            panic!("Account is not allowlisted");
+        }
+        if self.mint_config.ft_contract_id.is_none() && msg::value() != price {
+            panic!("attached value is not equal the mint price");
+        }
+        // the value would be stuck in the contract, since the price is paid in tokens
+        if self.mint_config.ft_contract_id.is_some() && msg::value() != 0 {
+            panic!("value must not be attached when the mint price is in fungible tokens");
+        }
+        let minted = self.minted_in_phase.entry((index, *minter)).or_default();
+        if max_per_wallet.map_or(false, |max| *minted >= max) {
+            panic!("Mint limit per wallet is reached");
+        }
+        *minted += 1;
+        Some((index, price))
+    }
+
+    /// Returns the index of the latest started phase and the phase itself
+    fn current_phase(&self) -> Option<(u32, &MintPhase)> {
+        let now = exec::block_timestamp();
+        self.mint_config
+            .phases
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, phase)| phase.start <= now)
+            .map(|(index, phase)| (index as u32, phase))
+    }
+
+    /// Pays the mint price to the contract owner and returns whether it was paid
+    async fn pay_mint_price(&self, minter: &ActorId, price: u128) -> bool {
+        if price == 0 {
+            return true;
+        }
+        let ft_contract_id = match self.mint_config.ft_contract_id {
+            Some(ft_contract_id) => ft_contract_id,
+            None => {
+                msg::send(self.owner, "", price).expect("Couldn't send mint price");
+                return true;
+            }
         };
-        self.tokens
-            .token_metadata_by_id
-            .insert(self.token_id, metadata);
-        let balance = *self
-            .tokens
-            .balances
-            .get(&msg::source())
-            .unwrap_or(&U256::zero());
-        self.tokens
-            .balances
-            .insert(msg::source(), balance.saturating_add(U256::one()));
-
-        msg::reply(
-            NFTEvent::Transfer {
-                from: ZERO_ID,
-                to: msg::source(),
-                token_id: self.token_id,
+        let reply: Result<FTEvent, _> = msg::send_and_wait_for_reply(
+            ft_contract_id,
+            FTAction::TransferFrom {
+                from: *minter,
+                to: self.owner,
+                amount: price,
+                tx_id: None,
             },
             0,
         )
-        .unwrap();
+        .unwrap()
+        .await;
+        matches!(reply, Ok(FTEvent::TransferFrom { .. }))
     }
 
-    fn burn(&mut self, token_id: U256) {
-        if !self.tokens.exists(token_id) {
-            panic!("NonFungibleToken: Token does not exist");
-        }
-        self.check_owner(token_id);
-        self.tokens.token_approvals.remove(&token_id);
-        self.tokens.owner_by_id.remove(&token_id);
-        let balance = *self
-            .tokens
-            .balances
-            .get(&msg::source())
-            .unwrap_or(&U256::zero());
-        self.tokens
-            .balances
-            .insert(msg::source(), balance.saturating_sub(U256::one()));
-
-        msg::reply(
-            NFTEvent::Transfer {
-                from: msg::source(),
-                to: ZERO_ID,
-                token_id,
-            },
-            0,
-        )
-        .unwrap();
+    fn set_mint_config(&mut self, config: MintConfig) {
+        if config
+            .phases
+            .windows(2)
+            .any(|phases| phases[0].start > phases[1].start)
+        {
+            panic!("Mint phases must be sorted by start");
+        }
+        self.mint_config = config;
+        self.minted_in_phase.clear();
     }
 
-    fn nft_payout(&self, owner: &ActorId, amount: u128) {
-        let payouts: Payout = if self.royalties.is_some() {
-            self.royalties.as_ref().unwrap().payouts(owner, amount)
-        } else {
-            let mut single_payout = BTreeMap::new();
-            single_payout.insert(*owner, amount);
-            single_payout
+    fn redeem_voucher(
+        &mut self,
+        token_id: U256,
+        media: String,
+        reference: String,
+        min_price: u128,
+        signature: &[u8; 64],
+    ) {
+        let signer = self.voucher_signer.expect("Vouchers are disabled");
+        if token_id < self.token_id || self.redeemed_vouchers.contains(&token_id) {
+            panic!("Voucher is already used");
+        }
+        if msg::value() < min_price {
+            panic!("value < min price, {:?} < {:?}", msg::value(), min_price);
+        }
+        let payload = VoucherPayload {
+            nft: exec::program_id(),
+            token_id,
+            media,
+            reference,
+            min_price,
         };
+        verify_signature(&signer, &payload.encode(), signature);
+        self.check_supply();
+
+        self.redeemed_vouchers.insert(token_id);
+        self.tokens.mint_to(
+            &msg::source(),
+            token_id,
+            Some(metadata(payload.media, payload.reference)),
+        );
+        msg::send(signer, "", msg::value()).expect("Couldn't send payment for voucher signer");
+    }
+
+    fn check_supply(&mut self) {
+        if self.minted >= self.supply {
+            panic!("No tokens left");
+        }
+        self.minted = self.minted.saturating_add(U256::one());
+    }
+
+    fn nft_payout(&self, owner: &ActorId, token_id: U256, amount: u128) {
+        let payouts = self.tokens.payouts(token_id, owner, amount);
         msg::reply(NFTEvent::NFTPayout(payouts), 0).unwrap();
     }
 
-    fn check_owner(&self, token_id: U256) {
-        if self.tokens.owner_by_id.get(&token_id).unwrap_or(&ZERO_ID) != &msg::source()
-            || self.tokens.owner_by_id.get(&token_id).unwrap_or(&ZERO_ID) != &exec::origin()
-        {
-            panic!("Only owner can transfer");
+    fn check_owner(&self) {
+        if msg::source() != self.owner {
+            panic!("Only owner can make that action");
         }
     }
 }
@@ -134,24 +218,33 @@ async fn main() {
     let nft: &mut NFT = unsafe { CONTRACT.get_or_insert(NFT::default()) };
     match action {
         NFTAction::Mint { media, reference } => {
-            nft.mint(media, reference);
+            nft.mint(media, reference).await;
         }
         NFTAction::Burn(token_id) => {
-            nft.burn(token_id);
+            nft.tokens.burn(token_id);
         }
         NFTAction::Transfer { to, token_id } => {
             nft.tokens.transfer(&to, token_id);
         }
+        NFTAction::SafeTransfer { to, token_id, data } => {
+            nft.tokens.safe_transfer(&to, token_id, data).await;
+        }
         NFTAction::TokensForOwner(account) => {
-            let tokens = nft.owner_to_ids.get(&account).unwrap_or(&vec![]).clone();
-            msg::reply(NFTEvent::TokensForOwner(tokens), 0).unwrap();
+            nft.tokens.tokens_for_owner(&account);
         }
-        NFTAction::NFTPayout { owner, amount } => {
-            nft.nft_payout(&owner, amount);
+        NFTAction::NFTPayout {
+            owner,
+            token_id,
+            amount,
+        } => {
+            nft.nft_payout(&owner, token_id, amount);
         }
         NFTAction::Approve { to, token_id } => {
             nft.tokens.approve(&to, token_id);
         }
+        NFTAction::RevokeApproval(token_id) => {
+            nft.tokens.revoke_approval(token_id);
+        }
         NFTAction::OwnerOf(token_id) => {
             let owner = nft.tokens.owner_by_id.get(&token_id).unwrap_or(&ZERO_ID);
             msg::reply(NFTEvent::OwnerOf(*owner), 0).unwrap();
@@ -159,6 +252,114 @@ async fn main() {
         NFTAction::BalanceOf(account) => {
             nft.tokens.balance_of(&account);
         }
+        NFTAction::ApproveForAll { to, approved } => {
+            nft.tokens.approve_for_all(&msg::source(), &to, approved);
+        }
+        NFTAction::IsApprovedForAll { owner, operator } => {
+            nft.tokens.is_approved_for_all(&owner, &operator);
+        }
+        NFTAction::TotalSupply => {
+            nft.tokens.total_supply();
+        }
+        NFTAction::TokenByIndex(index) => {
+            nft.tokens.token_by_index(index);
+        }
+        NFTAction::TokenURI(token_id) => {
+            nft.tokens.token_uri(token_id);
+        }
+        NFTAction::SetTokenMetadata {
+            token_id,
+            metadata,
+            freeze,
+        } => {
+            nft.check_owner();
+            nft.tokens.set_token_metadata(token_id, metadata, freeze);
+        }
+        NFTAction::RoyaltyInfo {
+            token_id,
+            sale_price,
+        } => {
+            nft.tokens.royalty_info(token_id, sale_price);
+        }
+        NFTAction::SetTokenRoyalties {
+            token_id,
+            royalties,
+        } => {
+            nft.check_owner();
+            nft.tokens.set_token_royalties(token_id, royalties);
+        }
+        NFTAction::RedeemVoucher {
+            token_id,
+            media,
+            reference,
+            min_price,
+            signature,
+        } => {
+            nft.redeem_voucher(token_id, media, reference, min_price, &signature);
+        }
+        NFTAction::SetAttribute {
+            token_id,
+            key,
+            value,
+        } => {
+            nft.tokens.set_attribute(token_id, key, value);
+        }
+        NFTAction::RemoveAttribute { token_id, key } => {
+            nft.tokens.remove_attribute(token_id, key);
+        }
+        NFTAction::SetAttributeUpdater { account, allowed } => {
+            nft.check_owner();
+            nft.tokens.set_attribute_updater(&account, allowed);
+            msg::reply(NFTEvent::AttributeUpdaterSet { account, allowed }, 0).unwrap();
+        }
+        NFTAction::SetMintConfig(config) => {
+            nft.check_owner();
+            nft.set_mint_config(config);
+            msg::reply(NFTEvent::MintConfigSet, 0).unwrap();
+        }
+        NFTAction::AddToAllowlist(accounts) => {
+            nft.check_owner();
+            nft.allowlist.extend(accounts.iter().copied());
+            msg::reply(NFTEvent::AddedToAllowlist(accounts), 0).unwrap();
+        }
+        NFTAction::RemoveFromAllowlist(accounts) => {
+            nft.check_owner();
+            for account in &accounts {
+                nft.allowlist.remove(account);
+            }
+            msg::reply(NFTEvent::RemovedFromAllowlist(accounts), 0).unwrap();
+        }
+    }
+}
+
+/// Returns the token metadata, the empty `media` and `reference` are not stored,
+/// so the token URI falls back to the base URI
+fn metadata(media: String, reference: String) -> TokenMetadata {
+    TokenMetadata {
+        title: None,
+        description: None,
+        media: non_empty(media),
+        reference: non_empty(reference),
+    }
+}
+
+fn non_empty(value: String) -> Option<String> {
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// Checks that `signature` is the sr25519 signature of `signer` over `message`.
+fn verify_signature(signer: &ActorId, message: &[u8], signature: &[u8; 64]) {
+    let public_key = PublicKey::from_bytes(signer.as_ref()).expect("Invalid voucher signer");
+    let signature = Signature::from_bytes(signature).expect("Invalid signature");
+    if public_key
+        .verify_simple(SIGNING_CONTEXT, message, &signature)
+        .is_err()
+    {
+        panic!("Invalid signature");
     }
 }
 
@@ -169,8 +370,88 @@ pub unsafe extern "C" fn init() {
         tokens: NonFungibleToken::new(),
         owner: msg::source(),
         supply: config.supply,
+        voucher_signer: config.voucher_signer,
         ..NFT::default()
     };
+    nft.set_mint_config(config.mint_config);
     nft.tokens.init(config.name, config.symbol, config.base_uri);
+    nft.tokens.set_royalties(config.royalties);
+    nft.tokens.history_retention = config.history_retention;
     CONTRACT = Some(nft);
 }
+
+#[no_mangle]
+pub unsafe extern "C" fn meta_state() -> *mut [i32; 2] {
+    let query: State = msg::load().expect("failed to decode input argument");
+    let nft: &mut NFT = CONTRACT.get_or_insert(NFT::default());
+    let owner_of = |token_id| *nft.tokens.owner_by_id.get(&token_id).unwrap_or(&ZERO_ID);
+    let encoded = match query {
+        State::BalanceOfUser(user) => StateReply::BalanceOfUser(
+            *nft.tokens
+                .balances
+                .get(&ActorId::new(user.to_fixed_bytes()))
+                .unwrap_or(&U256::zero()),
+        ),
+        State::TokenOwner(token_id) => {
+            StateReply::TokenOwner(H256::from_slice(owner_of(token_id).as_ref()))
+        }
+        State::IsTokenOwner(TokenAndUser { token_id, user }) => {
+            StateReply::IsTokenOwner(owner_of(token_id) == ActorId::new(user.to_fixed_bytes()))
+        }
+        State::GetApproved(token_id) => {
+            let approved = nft
+                .tokens
+                .token_approvals
+                .get(&token_id)
+                .unwrap_or(&ZERO_ID);
+            StateReply::GetApproved(H256::from_slice(approved.as_ref()))
+        }
+        State::TokenMetadata(token_id) => {
+            StateReply::TokenMetadata(nft.tokens.token_metadata_by_id.get(&token_id).cloned())
+        }
+        State::TokenAttributes(token_id) => {
+            StateReply::TokenAttributes(nft.tokens.attributes_of(token_id))
+        }
+        State::TokensWithAttribute { key, value } => {
+            StateReply::Tokens(nft.tokens.tokens_with_attribute(&key, value.as_ref()))
+        }
+        State::TokenHistory {
+            token_id,
+            offset,
+            limit,
+        } => StateReply::History(nft.tokens.token_history(token_id, offset, limit)),
+        State::AccountActivity {
+            account,
+            offset,
+            limit,
+        } => StateReply::History(nft.tokens.account_activity(
+            &ActorId::new(account.to_fixed_bytes()),
+            offset,
+            limit,
+        )),
+        State::ContractMetadata => StateReply::ContractMetadata {
+            name: nft.tokens.name.clone(),
+            symbol: nft.tokens.symbol.clone(),
+            base_uri: nft.tokens.base_uri.clone(),
+        },
+        State::IsVoucherRedeemed(token_id) => {
+            StateReply::IsVoucherRedeemed(nft.redeemed_vouchers.contains(&token_id))
+        }
+        State::MintConfig => StateReply::MintConfig {
+            config: nft.mint_config.clone(),
+            current_phase: nft.current_phase().map(|(index, _)| index),
+        },
+        State::IsAllowlisted(account) => StateReply::IsAllowlisted(
+            nft.allowlist
+                .contains(&ActorId::new(account.to_fixed_bytes())),
+        ),
+        State::MintedInPhase { account, phase } => StateReply::MintedInPhase(
+            *nft.minted_in_phase
+                .get(&(phase, ActorId::new(account.to_fixed_bytes())))
+                .unwrap_or(&0),
+        ),
+    }
+    .encode();
+
+    gstd::util::to_leak_ptr(encoded)
+}
@@ -1,5 +1,9 @@
 use codec::{Decode, Encode};
 use gstd::prelude::*;
+use nft_io::MintConfig;
+use non_fungible_token::attributes::AttributeValue;
+use non_fungible_token::history::HistoryRecord;
+use non_fungible_token::token::TokenMetadata;
 use primitive_types::{H256, U256};
 use scale_info::TypeInfo;
 
@@ -9,6 +13,34 @@ pub enum State {
     TokenOwner(U256),
     IsTokenOwner(TokenAndUser),
     GetApproved(U256),
+    TokenMetadata(U256),
+    ContractMetadata,
+    /// Returns at most `limit` history records of the token from the oldest one, skipping the first `offset`
+    TokenHistory {
+        token_id: U256,
+        offset: u32,
+        limit: u32,
+    },
+    /// Returns at most `limit` history records involving the account from the oldest one, skipping the first `offset`
+    AccountActivity {
+        account: H256,
+        offset: u32,
+        limit: u32,
+    },
+    IsVoucherRedeemed(U256),
+    MintConfig,
+    IsAllowlisted(H256),
+    /// Returns the number of tokens the account minted during the phase
+    MintedInPhase {
+        account: H256,
+        phase: u32,
+    },
+    TokenAttributes(U256),
+    /// Returns the tokens having the attribute, with the given value if it is set
+    TokensWithAttribute {
+        key: String,
+        value: Option<AttributeValue>,
+    },
 }
 
 #[derive(Debug, Decode, Encode, TypeInfo)]
@@ -17,6 +49,22 @@ pub enum StateReply {
     TokenOwner(H256),
     IsTokenOwner(bool),
     GetApproved(H256),
+    TokenMetadata(Option<TokenMetadata>),
+    ContractMetadata {
+        name: String,
+        symbol: String,
+        base_uri: String,
+    },
+    History(Vec<HistoryRecord>),
+    IsVoucherRedeemed(bool),
+    MintConfig {
+        config: MintConfig,
+        current_phase: Option<u32>,
+    },
+    IsAllowlisted(bool),
+    MintedInPhase(u32),
+    TokenAttributes(BTreeMap<String, AttributeValue>),
+    Tokens(Vec<U256>),
 }
 
 #[derive(Debug, Decode, Encode, TypeInfo)]
@@ -0,0 +1,30 @@
+use codec::{Decode, Encode};
+use gstd::prelude::*;
+use primitive_types::U256;
+use scale_info::TypeInfo;
+
+/// The value of a token attribute, for example the level or the class of a game item
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum AttributeValue {
+    Int(i64),
+    Bool(bool),
+    Text(String),
+}
+
+pub trait NonFungibleTokenAttributes {
+    /// Sets the attribute of the token replacing its previous value
+    /// Arguments:
+    /// * `token_id`: the token ID
+    /// * `key`: the attribute name
+    /// * `value`: the new attribute value
+    /// Contract must panic if the token does not exist or the caller is not an attribute updater
+    fn set_attribute(&mut self, token_id: U256, key: String, value: AttributeValue);
+
+    /// Removes the attribute of the token
+    /// Arguments:
+    /// * `token_id`: the token ID
+    /// * `key`: the attribute name
+    /// Contract must panic if the token does not exist, doesn't have the attribute
+    /// or the caller is not an attribute updater
+    fn remove_attribute(&mut self, token_id: U256, key: String);
+}
@@ -1,3 +1,4 @@
+use crate::token::TokenMetadata;
 use gstd::{prelude::*, ActorId};
 use primitive_types::U256;
 
@@ -13,22 +14,39 @@ pub trait NonFungibleTokenBase {
     /// Arguments:
     /// * `token_id`: the ID of the token to transfer
     /// * `to`: the valid ActorId, the account to which the token will be sent
-    /// Contract must panic if `from` is neither the token owner nor the approved actor for the token. It also must panic if `to` is a zero ID
+    /// Contract must panic if `from` is neither the token owner nor the approved actor for the token. It also must panic if `to` is a zero ID or the token is not transferable
+    /// To transfer a token to a program that must be notified, see `NonFungibleToken::safe_transfer`,
+    /// which isn't a method of this trait since it waits for the receiver reply and can't be sync
     fn transfer(&mut self, to: &ActorId, token_id: U256);
 
+    /// Creates a new NFT item and gives it to `to`
+    /// Arguments:
+    /// * `to`: the valid ActorId, the account that will own the token
+    /// * `token_id`: the ID of the new token
+    /// * `metadata`: the metadata of the new token, if any
+    /// Contract must panic if the token already exists or `to` is a zero ID
+    fn mint_to(&mut self, to: &ActorId, token_id: U256, metadata: Option<TokenMetadata>);
+
+    /// Destroys an NFT item together with its metadata and approval
+    /// Arguments:
+    /// * `token_id`: the ID of the token to burn
+    /// Contract must panic if the token does not exist or the caller is not the token owner
+    fn burn(&mut self, token_id: U256);
+
     /// Gives a right to the actor to manage the specific token
     /// Arguments:
     /// * `token_id`: the token ID
     /// * `to`: the valid ActorId that will be approved to manage the token
-    /// Contract must panic if `owner` is not the token owner or `spender` is a zero ID
+    /// Contract must panic if `owner` is not the token owner, `spender` is a zero ID or the token is not transferable
     fn approve(&mut self, to: &ActorId, token_id: U256);
 
     /// Enables or disables the actor to manage all the tokens the owner has
+    /// The owner can have several operators, revoking one of them doesn't affect the others
     /// Arguments:
     /// * `owner`: the valid ActorId that must be the token owner
     /// * `operator`: the valid ActorId that will be approved to manage the tokens
     /// * `approved`: True if the operator is approved, false to revoke approval
-    /// Contract must panic if `owner` is not the token owner or `operator` is a zero ID
+    /// Contract must panic if `owner` is not the token owner, `operator` is a zero ID or the approval is given for non-transferable tokens
     fn approve_for_all(&mut self, owner: &ActorId, operator: &ActorId, approved: bool);
 
     /// Sends a message including the information about the balance of `account`
@@ -41,4 +59,11 @@ pub trait NonFungibleTokenBase {
     /// Arguments:
     /// * `token_id`: the token ID
     fn owner_of(&self, token_id: U256);
+
+    /// Sends a message including the information whether `operator` is approved
+    /// to manage all the tokens of `owner`
+    /// Arguments:
+    /// * `owner`: the valid ActorId
+    /// * `operator`: the valid ActorId
+    fn is_approved_for_all(&self, owner: &ActorId, operator: &ActorId);
 }
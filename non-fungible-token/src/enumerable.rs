@@ -0,0 +1,18 @@
+use gstd::ActorId;
+use primitive_types::U256;
+
+pub trait NonFungibleTokenEnumerable {
+    /// Sends a message including the IDs of all the tokens `owner` has
+    /// Arguments:
+    /// * `owner`: the valid ActorId
+    fn tokens_for_owner(&self, owner: &ActorId);
+
+    /// Sends a message including the number of existing tokens
+    fn total_supply(&self);
+
+    /// Sends a message including the ID of the token at `index` among all the tokens ordered by ID
+    /// Arguments:
+    /// * `index`: the index of the token, must be less than the total supply
+    /// Contract must panic if `index` is out of bounds
+    fn token_by_index(&self, index: U256);
+}
@@ -0,0 +1,23 @@
+use codec::{Decode, Encode};
+use gstd::ActorId;
+use primitive_types::U256;
+use scale_info::TypeInfo;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum HistoryKind {
+    /// the token was transferred, minted (`from` is zero) or burned (`to` is zero)
+    Transfer,
+    /// `to` was approved to manage the token of `from`
+    Approval,
+}
+
+/// A change of the token ownership or approval kept by the library if the history is enabled
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct HistoryRecord {
+    pub kind: HistoryKind,
+    pub token_id: U256,
+    pub from: ActorId,
+    pub to: ActorId,
+    pub block_height: u32,
+    pub timestamp: u64,
+}
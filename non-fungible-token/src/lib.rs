@@ -5,6 +5,18 @@ use codec::{Decode, Encode};
 use gstd::{exec, msg, prelude::*, ActorId};
 pub mod base;
 use base::NonFungibleTokenBase;
+pub mod enumerable;
+use enumerable::NonFungibleTokenEnumerable;
+pub mod receiver;
+use receiver::{NFTReceiverAction, NFTReceiverEvent};
+pub mod metadata;
+use metadata::NonFungibleTokenMetadata;
+pub mod royalties;
+use royalties::{NonFungibleTokenRoyalties, Payout, Royalties};
+pub mod attributes;
+pub mod history;
+use attributes::{AttributeValue, NonFungibleTokenAttributes};
+use history::{HistoryKind, HistoryRecord};
 pub mod token;
 use token::TokenMetadata;
 
@@ -13,7 +25,7 @@ use scale_info::TypeInfo;
 
 const ZERO_ID: ActorId = ActorId::new([0u8; 32]);
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct NonFungibleToken {
     pub name: String,
     pub symbol: String,
@@ -22,7 +34,28 @@ pub struct NonFungibleToken {
     pub token_metadata_by_id: BTreeMap<U256, TokenMetadata>,
     pub token_approvals: BTreeMap<U256, ActorId>,
     pub balances: BTreeMap<ActorId, U256>,
-    pub operator_approval: BTreeMap<ActorId, ActorId>,
+    pub operator_approvals: BTreeMap<ActorId, BTreeSet<ActorId>>,
+    pub owner_to_ids: BTreeMap<ActorId, BTreeSet<U256>>,
+    pub frozen_metadata: BTreeSet<U256>,
+    pub royalties: Option<Royalties>,
+    pub token_royalties: BTreeMap<U256, Royalties>,
+    /// the number of the latest records kept per token and per account, zero disables the history
+    pub history_retention: u32,
+    pub token_history: BTreeMap<U256, Vec<HistoryRecord>>,
+    pub account_activity: BTreeMap<ActorId, Vec<HistoryRecord>>,
+    /// false makes the tokens soulbound: they can't be transferred or approved, only burned or revoked
+    pub transferable: bool,
+    /// the tokens overriding the collection `transferable` flag
+    pub token_transferable: BTreeMap<U256, bool>,
+    pub attributes: BTreeMap<U256, BTreeMap<String, AttributeValue>>,
+    /// the accounts allowed to change the token attributes
+    pub attribute_updaters: BTreeSet<ActorId>,
+}
+
+impl Default for NonFungibleToken {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl NonFungibleTokenBase for NonFungibleToken {
@@ -33,43 +66,37 @@ impl NonFungibleTokenBase for NonFungibleToken {
     }
 
     fn transfer(&mut self, to: &ActorId, token_id: U256) {
-        if !self.exists(token_id) {
-            panic!("NonFungibleToken: token does not exist");
-        }
+        let owner = self.transfer_token(to, token_id);
 
-        if to == &ZERO_ID {
-            panic!("NonFungibleToken: Transfer to zero address.");
-        }
-
-        let owner = *self.owner_by_id.get(&token_id).unwrap_or(&ZERO_ID);
+        msg::reply(
+            Event::Transfer {
+                from: owner,
+                to: *to,
+                token_id,
+            },
+            0,
+        )
+        .unwrap();
+    }
 
-        if &owner == to {
-            panic!("NonFungibleToken: Transfer to current owner");
+    fn mint_to(&mut self, to: &ActorId, token_id: U256, metadata: Option<TokenMetadata>) {
+        if self.exists(token_id) {
+            panic!("NonFungibleToken: token already exists");
         }
 
-        match self.authorized_actor(token_id, &owner) {
-            AuthAccount::None => {
-                panic!("NonFungibleToken: is not an authorized source");
-            }
-            AuthAccount::ApprovedActor => {
-                self.token_approvals.remove(&token_id);
-            }
-            _ => {}
+        if to == &ZERO_ID {
+            panic!("NonFungibleToken: Mint to zero address.");
         }
 
-        let from_balance = *self.balances.get(&owner).unwrap_or(&U256::zero());
-        let to_balance = *self.balances.get(to).unwrap_or(&U256::zero());
-
-        self.balances
-            .insert(owner, from_balance.saturating_sub(U256::one()));
-        self.balances
-            .insert(*to, to_balance.saturating_add(U256::one()));
-
-        self.owner_by_id.insert(token_id, *to);
+        self.add_token_to(to, token_id);
+        self.record(HistoryKind::Transfer, token_id, &ZERO_ID, to);
+        if let Some(metadata) = metadata {
+            self.token_metadata_by_id.insert(token_id, metadata);
+        }
 
         msg::reply(
             Event::Transfer {
-                from: owner,
+                from: ZERO_ID,
                 to: *to,
                 token_id,
             },
@@ -78,26 +105,45 @@ impl NonFungibleTokenBase for NonFungibleToken {
         .unwrap();
     }
 
+    fn burn(&mut self, token_id: U256) {
+        if !self.exists(token_id) {
+            panic!("NonFungibleToken: token does not exist");
+        }
+
+        let owner = *self.owner_by_id.get(&token_id).unwrap_or(&ZERO_ID);
+
+        if !self.is_token_owner(&owner) {
+            panic!("NonFungibleToken: is not owner");
+        }
+
+        self.burn_token(&owner, token_id);
+    }
+
     fn approve(&mut self, to: &ActorId, token_id: U256) {
         if to == &ZERO_ID {
             panic!("NonFungibleToken: Approval to zero address.");
         }
 
-        let owner = self.owner_by_id.get(&token_id).unwrap_or(&ZERO_ID);
+        let owner = *self.owner_by_id.get(&token_id).unwrap_or(&ZERO_ID);
 
-        if to == owner {
+        if to == &owner {
             panic!("NonFungibleToken: Approval to current owner");
         }
 
-        if !self.is_token_owner(owner) {
+        if !self.is_token_owner(&owner) {
             panic!("NonFungibleToken: is not owner");
         }
 
+        if !self.is_transferable(token_id) {
+            panic!("NonFungibleToken: token is not transferable");
+        }
+
         self.token_approvals.insert(token_id, *to);
+        self.record(HistoryKind::Approval, token_id, &owner, to);
 
         msg::reply(
             Event::Approval {
-                from: *owner,
+                from: owner,
                 to: *to,
                 token_id,
             },
@@ -110,10 +156,20 @@ impl NonFungibleTokenBase for NonFungibleToken {
         if operator == &ZERO_ID {
             panic!("NonFungibleToken: Approval for a zero address");
         }
-        match approved {
-            true => self.operator_approval.insert(*owner, *operator),
-            false => self.operator_approval.remove(owner),
-        };
+        if approved && !self.transferable {
+            panic!("NonFungibleToken: tokens are not transferable");
+        }
+        if approved {
+            self.operator_approvals
+                .entry(*owner)
+                .or_default()
+                .insert(*operator);
+        } else if let Some(operators) = self.operator_approvals.get_mut(owner) {
+            operators.remove(operator);
+            if operators.is_empty() {
+                self.operator_approvals.remove(owner);
+            }
+        }
 
         msg::reply(
             Event::ApprovalForAll {
@@ -135,6 +191,14 @@ impl NonFungibleTokenBase for NonFungibleToken {
         let owner = self.owner_by_id.get(&token_id).unwrap_or(&ZERO_ID);
         msg::reply(Event::OwnerOf(*owner), 0).unwrap();
     }
+
+    fn is_approved_for_all(&self, owner: &ActorId, operator: &ActorId) {
+        msg::reply(
+            Event::IsApprovedForAll(self.is_operator(owner, operator)),
+            0,
+        )
+        .unwrap();
+    }
 }
 
 impl NonFungibleToken {
@@ -147,7 +211,18 @@ impl NonFungibleToken {
             token_metadata_by_id: BTreeMap::new(),
             token_approvals: BTreeMap::new(),
             balances: BTreeMap::new(),
-            operator_approval: BTreeMap::new(),
+            operator_approvals: BTreeMap::new(),
+            owner_to_ids: BTreeMap::new(),
+            frozen_metadata: BTreeSet::new(),
+            royalties: None,
+            token_royalties: BTreeMap::new(),
+            history_retention: 0,
+            token_history: BTreeMap::new(),
+            account_activity: BTreeMap::new(),
+            transferable: true,
+            token_transferable: BTreeMap::new(),
+            attributes: BTreeMap::new(),
+            attribute_updaters: BTreeSet::new(),
         }
     }
 
@@ -162,15 +237,486 @@ impl NonFungibleToken {
         if self.token_approvals.get(&token_id).unwrap_or(&ZERO_ID) == &msg::source() {
             return AuthAccount::ApprovedActor;
         }
-        if self.operator_approval.contains_key(owner) {
+        if self.is_operator(owner, &msg::source()) {
             return AuthAccount::Operator;
         }
         AuthAccount::None
     }
 
+    /// Checks whether `owner` allowed `operator` to manage all of its tokens.
+    pub fn is_operator(&self, owner: &ActorId, operator: &ActorId) -> bool {
+        self.operator_approvals
+            .get(owner)
+            .map_or(false, |operators| operators.contains(operator))
+    }
+
     pub fn exists(&self, token_id: U256) -> bool {
         self.owner_by_id.contains_key(&token_id)
     }
+
+    /// Transfers an NFT item from the current owner to `to` after the checks of
+    /// `NonFungibleTokenBase::transfer` and returns the previous owner
+    pub fn transfer_token(&mut self, to: &ActorId, token_id: U256) -> ActorId {
+        if !self.exists(token_id) {
+            panic!("NonFungibleToken: token does not exist");
+        }
+
+        if to == &ZERO_ID {
+            panic!("NonFungibleToken: Transfer to zero address.");
+        }
+
+        if !self.is_transferable(token_id) {
+            panic!("NonFungibleToken: token is not transferable");
+        }
+
+        let owner = *self.owner_by_id.get(&token_id).unwrap_or(&ZERO_ID);
+
+        if &owner == to {
+            panic!("NonFungibleToken: Transfer to current owner");
+        }
+
+        if let AuthAccount::None = self.authorized_actor(token_id, &owner) {
+            panic!("NonFungibleToken: is not an authorized source");
+        }
+
+        self.remove_token_from(&owner, token_id);
+        self.add_token_to(to, token_id);
+        self.record(HistoryKind::Transfer, token_id, &owner, to);
+        owner
+    }
+
+    /// Transfers an NFT item to the program `to` and notifies it with `NFTReceiverAction::OnNftReceived`
+    /// The transfer is reverted if the program doesn't reply with `NFTReceiverEvent::NftReceived`
+    /// Since the receiver is called, `to` must be a program, use `transfer` for user accounts
+    /// Programs can't be told from user accounts except for the message origin,
+    /// which is always a user account, so the token is transferred to it without the receiver call
+    /// It isn't a `NonFungibleTokenBase` method, since trait methods can't be async
+    /// Arguments:
+    /// * `to`: the program to which the token will be sent
+    /// * `token_id`: the ID of the token to transfer
+    /// * `data`: arbitrary data passed to the receiver
+    pub async fn safe_transfer(&mut self, to: &ActorId, token_id: U256, data: Vec<u8>) {
+        // a user account never replies, so waiting for it would leave the message waiting forever
+        if to == &exec::origin() {
+            self.transfer(to, token_id);
+            return;
+        }
+        let from = self.transfer_token(to, token_id);
+
+        let reply: Result<NFTReceiverEvent, _> = msg::send_and_wait_for_reply(
+            *to,
+            NFTReceiverAction::OnNftReceived {
+                operator: msg::source(),
+                from,
+                token_id,
+                data,
+            },
+            0,
+        )
+        .expect("NonFungibleToken: Error in sending a message to the receiver")
+        .await;
+
+        if matches!(reply, Ok(NFTReceiverEvent::NftReceived { token_id: id }) if id == token_id) {
+            msg::reply(
+                Event::Transfer {
+                    from,
+                    to: *to,
+                    token_id,
+                },
+                0,
+            )
+            .unwrap();
+            return;
+        }
+
+        // the state changed before waiting for the reply is already saved, so panicking here
+        // wouldn't undo the transfer, and the receiver may have moved the token while owning it
+        if self.owner_by_id.get(&token_id) == Some(to) {
+            self.remove_token_from(to, token_id);
+            self.add_token_to(&from, token_id);
+            self.record(HistoryKind::Transfer, token_id, to, &from);
+        }
+        msg::reply(
+            Event::TransferRejected {
+                from,
+                to: *to,
+                token_id,
+            },
+            0,
+        )
+        .unwrap();
+    }
+
+    /// Removes the approval of the token, only the owner or the approved actor can revoke it
+    pub fn revoke_approval(&mut self, token_id: U256) {
+        let owner = *self
+            .owner_by_id
+            .get(&token_id)
+            .expect("NonFungibleToken: token does not exist");
+        if let Some(approved) = self.token_approvals.get(&token_id) {
+            if approved != &msg::source() && !self.is_token_owner(&owner) {
+                panic!("NonFungibleToken: is not owner or approved actor");
+            }
+            self.token_approvals.remove(&token_id);
+            self.record(HistoryKind::Approval, token_id, &owner, &ZERO_ID);
+        }
+
+        msg::reply(
+            Event::Approval {
+                from: owner,
+                to: ZERO_ID,
+                token_id,
+            },
+            0,
+        )
+        .unwrap();
+    }
+
+    /// Checks whether the token can be transferred and approved
+    pub fn is_transferable(&self, token_id: U256) -> bool {
+        *self
+            .token_transferable
+            .get(&token_id)
+            .unwrap_or(&self.transferable)
+    }
+
+    /// Overrides the collection `transferable` flag for the token, `None` removes the override
+    /// The caller must check that the issuer makes that action
+    pub fn set_token_transferable(&mut self, token_id: U256, transferable: Option<bool>) {
+        if !self.exists(token_id) {
+            panic!("NonFungibleToken: token does not exist");
+        }
+        match transferable {
+            Some(transferable) => {
+                self.token_transferable.insert(token_id, transferable);
+            }
+            None => {
+                self.token_transferable.remove(&token_id);
+            }
+        }
+    }
+
+    /// Burns the token regardless of its owner, even if it isn't transferable
+    /// The caller must check that the issuer makes that action
+    pub fn revoke(&mut self, token_id: U256) {
+        let owner = *self
+            .owner_by_id
+            .get(&token_id)
+            .expect("NonFungibleToken: token does not exist");
+        self.burn_token(&owner, token_id);
+    }
+
+    /// Allows or disallows `account` to change the token attributes
+    /// The caller must check that the issuer makes that action
+    pub fn set_attribute_updater(&mut self, account: &ActorId, allowed: bool) {
+        if allowed {
+            self.attribute_updaters.insert(*account);
+        } else {
+            self.attribute_updaters.remove(account);
+        }
+    }
+
+    /// Returns the attributes of the token
+    pub fn attributes_of(&self, token_id: U256) -> BTreeMap<String, AttributeValue> {
+        self.attributes.get(&token_id).cloned().unwrap_or_default()
+    }
+
+    /// Returns the IDs of the tokens having the attribute in ascending order,
+    /// if `value` is set, only the tokens with that attribute value are returned
+    pub fn tokens_with_attribute(&self, key: &str, value: Option<&AttributeValue>) -> Vec<U256> {
+        self.attributes
+            .iter()
+            .filter(|(_, attributes)| match (attributes.get(key), value) {
+                (Some(attribute), Some(value)) => attribute == value,
+                (attribute, None) => attribute.is_some(),
+                (None, Some(_)) => false,
+            })
+            .map(|(token_id, _)| *token_id)
+            .collect()
+    }
+
+    fn check_attribute_updater(&self, token_id: U256) {
+        if !self.exists(token_id) {
+            panic!("NonFungibleToken: token does not exist");
+        }
+        if !self.attribute_updaters.contains(&msg::source()) {
+            panic!("NonFungibleToken: is not an attribute updater");
+        }
+    }
+
+    /// Returns the URI of the token, see `NonFungibleTokenMetadata::token_uri`
+    pub fn uri(&self, token_id: U256) -> String {
+        if !self.exists(token_id) {
+            panic!("NonFungibleToken: token does not exist");
+        }
+        self.token_metadata_by_id
+            .get(&token_id)
+            .and_then(|metadata| {
+                [&metadata.reference, &metadata.media]
+                    .into_iter()
+                    .flatten()
+                    .find(|uri| !uri.is_empty())
+            })
+            .cloned()
+            .unwrap_or_else(|| format!("{}{}", self.base_uri, token_id))
+    }
+
+    /// Sets the collection royalties used for the tokens without their own royalties
+    pub fn set_royalties(&mut self, royalties: Option<Royalties>) {
+        if let Some(royalties) = &royalties {
+            royalties.validate();
+        }
+        self.royalties = royalties;
+    }
+
+    /// Returns the royalties of the token or the collection royalties if it has none
+    pub fn royalties_of(&self, token_id: U256) -> Option<&Royalties> {
+        self.token_royalties
+            .get(&token_id)
+            .or(self.royalties.as_ref())
+    }
+
+    /// Splits `amount` paid for the token between the royalty accounts and `owner`
+    pub fn payouts(&self, token_id: U256, owner: &ActorId, amount: u128) -> Payout {
+        match self.royalties_of(token_id) {
+            Some(royalties) => royalties.payouts(owner, amount),
+            None => [(*owner, amount)].into_iter().collect(),
+        }
+    }
+
+    /// Returns at most `limit` history records of the token from the oldest one, skipping the first `offset`
+    pub fn token_history(&self, token_id: U256, offset: u32, limit: u32) -> Vec<HistoryRecord> {
+        page(self.token_history.get(&token_id), offset, limit)
+    }
+
+    /// Returns at most `limit` history records involving `account` from the oldest one, skipping the first `offset`
+    pub fn account_activity(
+        &self,
+        account: &ActorId,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<HistoryRecord> {
+        page(self.account_activity.get(account), offset, limit)
+    }
+
+    fn record(&mut self, kind: HistoryKind, token_id: U256, from: &ActorId, to: &ActorId) {
+        if self.history_retention == 0 {
+            return;
+        }
+        let record = HistoryRecord {
+            kind,
+            token_id,
+            from: *from,
+            to: *to,
+            block_height: exec::block_height(),
+            timestamp: exec::block_timestamp(),
+        };
+        let retention = self.history_retention as usize;
+        push_bounded(
+            self.token_history.entry(token_id).or_default(),
+            record.clone(),
+            retention,
+        );
+        for account in [from, to] {
+            if account != &ZERO_ID {
+                push_bounded(
+                    self.account_activity.entry(*account).or_default(),
+                    record.clone(),
+                    retention,
+                );
+            }
+        }
+    }
+
+    /// Returns the IDs of the tokens `owner` has in ascending order.
+    pub fn owned_tokens(&self, owner: &ActorId) -> Vec<U256> {
+        self.owner_to_ids
+            .get(owner)
+            .map(|ids| ids.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the ID of the token at `index` among all the tokens in ascending order.
+    pub fn token_at(&self, index: U256) -> Option<U256> {
+        if index >= U256::from(self.owner_by_id.len()) {
+            return None;
+        }
+        self.owner_by_id.keys().nth(index.as_usize()).copied()
+    }
+
+    fn burn_token(&mut self, owner: &ActorId, token_id: U256) {
+        self.remove_token_from(owner, token_id);
+        self.record(HistoryKind::Transfer, token_id, owner, &ZERO_ID);
+        self.token_metadata_by_id.remove(&token_id);
+        self.frozen_metadata.remove(&token_id);
+        self.token_royalties.remove(&token_id);
+        self.token_transferable.remove(&token_id);
+        self.attributes.remove(&token_id);
+
+        msg::reply(
+            Event::Transfer {
+                from: *owner,
+                to: ZERO_ID,
+                token_id,
+            },
+            0,
+        )
+        .unwrap();
+    }
+
+    fn add_token_to(&mut self, to: &ActorId, token_id: U256) {
+        self.owner_by_id.insert(token_id, *to);
+        self.owner_to_ids.entry(*to).or_default().insert(token_id);
+        let balance = *self.balances.get(to).unwrap_or(&U256::zero());
+        self.balances
+            .insert(*to, balance.saturating_add(U256::one()));
+    }
+
+    fn remove_token_from(&mut self, from: &ActorId, token_id: U256) {
+        self.owner_by_id.remove(&token_id);
+        self.token_approvals.remove(&token_id);
+        if let Some(ids) = self.owner_to_ids.get_mut(from) {
+            ids.remove(&token_id);
+            if ids.is_empty() {
+                self.owner_to_ids.remove(from);
+            }
+        }
+        let balance = *self.balances.get(from).unwrap_or(&U256::zero());
+        self.balances
+            .insert(*from, balance.saturating_sub(U256::one()));
+    }
+}
+
+impl NonFungibleTokenMetadata for NonFungibleToken {
+    fn token_uri(&self, token_id: U256) {
+        msg::reply(Event::TokenURI(self.uri(token_id)), 0).unwrap();
+    }
+
+    fn set_token_metadata(&mut self, token_id: U256, metadata: TokenMetadata, freeze: bool) {
+        if !self.exists(token_id) {
+            panic!("NonFungibleToken: token does not exist");
+        }
+
+        if self.frozen_metadata.contains(&token_id) {
+            panic!("NonFungibleToken: metadata is frozen");
+        }
+
+        self.token_metadata_by_id.insert(token_id, metadata);
+        if freeze {
+            self.frozen_metadata.insert(token_id);
+        }
+
+        msg::reply(
+            Event::TokenMetadataSet {
+                token_id,
+                frozen: freeze,
+            },
+            0,
+        )
+        .unwrap();
+    }
+}
+
+/// Appends `record` to `records` dropping the oldest ones beyond `retention`
+fn push_bounded(records: &mut Vec<HistoryRecord>, record: HistoryRecord, retention: usize) {
+    records.push(record);
+    if records.len() > retention {
+        records.drain(..records.len() - retention);
+    }
+}
+
+fn page(records: Option<&Vec<HistoryRecord>>, offset: u32, limit: u32) -> Vec<HistoryRecord> {
+    records
+        .map(|records| {
+            records
+                .iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl NonFungibleTokenRoyalties for NonFungibleToken {
+    fn royalty_info(&self, token_id: U256, sale_price: u128) {
+        let royalty_info = self
+            .royalties_of(token_id)
+            .map(|royalties| royalties.royalty_info(sale_price))
+            .unwrap_or_default();
+        msg::reply(Event::RoyaltyInfo(royalty_info), 0).unwrap();
+    }
+
+    fn set_token_royalties(&mut self, token_id: U256, royalties: Option<Royalties>) {
+        if !self.exists(token_id) {
+            panic!("NonFungibleToken: token does not exist");
+        }
+
+        match royalties {
+            Some(royalties) => {
+                royalties.validate();
+                self.token_royalties.insert(token_id, royalties);
+            }
+            None => {
+                self.token_royalties.remove(&token_id);
+            }
+        }
+
+        msg::reply(Event::RoyaltiesSet { token_id }, 0).unwrap();
+    }
+}
+
+impl NonFungibleTokenAttributes for NonFungibleToken {
+    fn set_attribute(&mut self, token_id: U256, key: String, value: AttributeValue) {
+        self.check_attribute_updater(token_id);
+        self.attributes
+            .entry(token_id)
+            .or_default()
+            .insert(key.clone(), value.clone());
+
+        msg::reply(
+            Event::AttributeSet {
+                token_id,
+                key,
+                value,
+            },
+            0,
+        )
+        .unwrap();
+    }
+
+    fn remove_attribute(&mut self, token_id: U256, key: String) {
+        self.check_attribute_updater(token_id);
+        let attributes = self
+            .attributes
+            .get_mut(&token_id)
+            .expect("NonFungibleToken: token has no attributes");
+        if attributes.remove(&key).is_none() {
+            panic!("NonFungibleToken: token has no such attribute");
+        }
+        if attributes.is_empty() {
+            self.attributes.remove(&token_id);
+        }
+
+        msg::reply(Event::AttributeRemoved { token_id, key }, 0).unwrap();
+    }
+}
+
+impl NonFungibleTokenEnumerable for NonFungibleToken {
+    fn tokens_for_owner(&self, owner: &ActorId) {
+        msg::reply(Event::TokensForOwner(self.owned_tokens(owner)), 0).unwrap();
+    }
+
+    fn total_supply(&self) {
+        msg::reply(Event::TotalSupply(self.owner_by_id.len().into()), 0).unwrap();
+    }
+
+    fn token_by_index(&self, index: U256) {
+        let token_id = self
+            .token_at(index)
+            .expect("NonFungibleToken: index out of bounds");
+        msg::reply(Event::TokenByIndex(token_id), 0).unwrap();
+    }
 }
 
 #[derive(Debug, Encode, TypeInfo, Decode)]
@@ -192,6 +738,33 @@ pub enum Event {
     },
     OwnerOf(ActorId),
     BalanceOf(U256),
+    IsApprovedForAll(bool),
+    TokensForOwner(Vec<U256>),
+    TotalSupply(U256),
+    TokenByIndex(U256),
+    TransferRejected {
+        from: ActorId,
+        to: ActorId,
+        token_id: U256,
+    },
+    TokenURI(String),
+    TokenMetadataSet {
+        token_id: U256,
+        frozen: bool,
+    },
+    RoyaltyInfo(Payout),
+    RoyaltiesSet {
+        token_id: U256,
+    },
+    AttributeSet {
+        token_id: U256,
+        key: String,
+        value: AttributeValue,
+    },
+    AttributeRemoved {
+        token_id: U256,
+        key: String,
+    },
 }
 
 #[derive(Debug, Encode, TypeInfo)]
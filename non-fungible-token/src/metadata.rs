@@ -0,0 +1,21 @@
+use crate::token::TokenMetadata;
+use primitive_types::U256;
+
+pub trait NonFungibleTokenMetadata {
+    /// Sends a message including the URI of the token
+    /// It is the `reference` or the `media` of the token metadata if any of them is set,
+    /// or `base_uri` followed by the token ID otherwise
+    /// Arguments:
+    /// * `token_id`: the token ID
+    /// Contract must panic if the token does not exist
+    fn token_uri(&self, token_id: U256);
+
+    /// Replaces the metadata of the token
+    /// Contract is responsible for checking that the caller may change the metadata
+    /// Arguments:
+    /// * `token_id`: the token ID
+    /// * `metadata`: the new metadata of the token
+    /// * `freeze`: True if the metadata can't be changed anymore
+    /// Contract must panic if the token does not exist or its metadata is frozen
+    fn set_token_metadata(&mut self, token_id: U256, metadata: TokenMetadata, freeze: bool);
+}
@@ -0,0 +1,30 @@
+use codec::{Decode, Encode};
+use gstd::{prelude::*, ActorId};
+use primitive_types::U256;
+use scale_info::TypeInfo;
+
+/// The message a program receives when an NFT is transferred to it with `safe_transfer`
+/// Its variant has the fixed index 255, so a receiver accepts NFTs by adding
+/// the same variant with `#[codec(index = 255)]` to its own action enum
+#[derive(Debug, Encode, Decode, TypeInfo)]
+pub enum NFTReceiverAction {
+    #[codec(index = 255)]
+    OnNftReceived {
+        /// the account that initiated the transfer
+        operator: ActorId,
+        /// the previous owner of the token
+        from: ActorId,
+        token_id: U256,
+        /// arbitrary data passed to `safe_transfer`
+        data: Vec<u8>,
+    },
+}
+
+/// The reply a receiver sends to accept the token
+/// Like `NFTReceiverAction`, it must be declared with `#[codec(index = 255)]` in the receiver's event enum
+/// Any other reply or a panic rejects the token
+#[derive(Debug, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub enum NFTReceiverEvent {
+    #[codec(index = 255)]
+    NftReceived { token_id: U256 },
+}
@@ -0,0 +1,71 @@
+use codec::{Decode, Encode};
+use gstd::{prelude::*, ActorId};
+use primitive_types::U256;
+use scale_info::TypeInfo;
+
+/// Royalty paid to `accounts` from every sale
+/// Percents are given in hundredths of a percent, so 10_000 is 100%
+#[derive(Debug, Decode, Encode, TypeInfo, Clone, PartialEq, Eq)]
+pub struct Royalties {
+    /// the share of the royalty each account receives
+    pub accounts: BTreeMap<ActorId, u16>,
+    /// the share of the sale price paid as the royalty
+    pub percent: u16,
+}
+
+pub type Payout = BTreeMap<ActorId, u128>;
+
+impl Royalties {
+    pub fn validate(&self) {
+        // percent must be less then 100% (100 * 100)
+        if self.percent > 10_000u16 {
+            panic!("royalty percent must be less than 100%");
+        }
+        let mut total_percents = 0;
+        self.accounts.iter().for_each(|(_, percent)| {
+            if *percent > 10_000u16 {
+                panic!("account percent must be less than 100%");
+            }
+            total_percents += percent;
+        });
+        if total_percents > 10_000u16 {
+            panic!("total percent of royalty be less than 100%");
+        }
+    }
+
+    /// Returns the royalty each account receives from a sale for `sale_price`
+    pub fn royalty_info(&self, sale_price: u128) -> Payout {
+        let royalty_payment = sale_price * self.percent as u128 / 10_000;
+        self.accounts
+            .iter()
+            .map(|(account, percent)| (*account, *percent as u128 * royalty_payment / 10_000))
+            .collect()
+    }
+
+    /// Returns the royalty payouts with `amount` less the whole royalty paid to `owner`
+    /// The royalty shares not given to any account and the rounding aren't paid to `owner`
+    pub fn payouts(&self, owner: &ActorId, amount: u128) -> Payout {
+        let mut payouts = self.royalty_info(amount);
+        let rest = amount - amount * self.percent as u128 / 10_000;
+        let owner_payout = payouts.get(owner).map_or(0, |p| *p) + rest;
+        payouts.insert(*owner, owner_payout);
+        payouts
+    }
+}
+
+pub trait NonFungibleTokenRoyalties {
+    /// Sends a message including the royalty each account receives from a sale of the token
+    /// The token royalties are used if they are set, and the collection royalties otherwise
+    /// Arguments:
+    /// * `token_id`: the token ID
+    /// * `sale_price`: the price the token is sold for
+    fn royalty_info(&self, token_id: U256, sale_price: u128);
+
+    /// Sets the royalties of the token overriding the collection royalties
+    /// Contract is responsible for checking that the caller may change the royalties
+    /// Arguments:
+    /// * `token_id`: the token ID
+    /// * `royalties`: the token royalties, `None` to use the collection royalties again
+    /// Contract must panic if the token does not exist or the royalties are invalid
+    fn set_token_royalties(&mut self, token_id: U256, royalties: Option<Royalties>);
+}
@@ -2,7 +2,7 @@ use codec::{Decode, Encode};
 use gstd::prelude::*;
 use scale_info::TypeInfo;
 
-#[derive(Debug, Decode, Encode, TypeInfo)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Decode, Encode, TypeInfo)]
 pub struct TokenMetadata {
     /// The title of NFT Item: for example "CryptoKitty #2505"
     pub title: Option<String>,